@@ -30,6 +30,53 @@ struct You {
     b: [u8; 16],
 }
 
+#[derive(LowerHexIter, FromHexIter, Display, Debug, PartialEq)]
+#[display_from(LowerHex)]
+#[hex(separator = ":")]
+struct Mac([u8; 6]);
+
+#[derive(LowerHexIter, FromHexIter, Debug, PartialEq)]
+#[hex(groups = [4, 2, 2, 2, 6], separator = "-")]
+struct Uuid([u8; 16]);
+
+#[derive(LowerHexIter, FromHexIter, Debug, PartialEq)]
+#[hex(separator = " ", groups = [2])]
+struct Fingerprint(Vec<u8>);
+
+#[derive(LowerHexIter, FromHexIter, Debug, PartialEq)]
+#[hex(groups = [2, 2], separator = "-")]
+struct Serial {
+    #[wrap]
+    bytes: [u8; 4],
+    revision: u8,
+}
+
+#[cfg(not(MSRV))]
+#[derive(LowerHexIter, UpperHexIter, FromHexIter, Debug, PartialEq)]
+#[hex(element = "be", separator = " ")]
+struct Midstate([u32; 4]);
+
+#[cfg(not(MSRV))]
+#[derive(LowerHexIter, FromHexIter, Debug, PartialEq)]
+#[hex(element = "le")]
+struct Words([u16; 3]);
+
+#[derive(LowerHexIter, UpperHexIter, OctalIter, BinaryIter)]
+#[hex(separator = "\u{2219}", groups = [3])]
+struct Dotted(Vec<u8>);
+
+#[derive(UpperHex, Octal, Binary, Display)]
+#[display_from(UpperHex)]
+struct Register(u16);
+
+#[derive(LowerExp, UpperExp, Display)]
+#[display_from(UpperExp)]
+struct Meters(f64);
+
+#[derive(UpperHexIter, OctalIter, BinaryIter, Display)]
+#[display_from(Binary)]
+struct Bits([u8; 2]);
+
 #[derive(Debug, AsRef, Default, LowerHex, Display, PartialEq)]
 #[display_from(LowerHex)]
 struct Other {
@@ -66,6 +113,473 @@ enum CustomErr<E: std::error::Error> {
     Other(E),
 }
 
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+struct ReadError {
+    path: String,
+    #[source]
+    cause: io::Error,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+#[wrap = "inner"]
+struct Wrapped {
+    inner: ReadError,
+    context: &'static str,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+struct Maybe(#[source] Option<io::Error>);
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+struct Boxed {
+    #[source]
+    cause: Box<dyn Error + Send + Sync>,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+#[error(transparent)]
+struct Opaque(ConfigError);
+
+#[cfg(not(MSRV))]
+#[derive(Error)]
+#[error(transparent, debug)]
+#[wrap = "inner"]
+struct Invisible {
+    inner: ReadError,
+    _retries: u8,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+enum Root {
+    #[error("root cause")]
+    Cause,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+#[error(chain)]
+enum Middle {
+    #[error("middle")]
+    #[derive_from]
+    Root(Root),
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+#[error(transparent, chain)]
+struct Transparent(Middle);
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+#[error(chain = " -> ")]
+enum App {
+    #[error("outer")]
+    #[derive_from]
+    Middle(Middle),
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+#[error(chain)]
+enum Forwarded {
+    #[error(transparent)]
+    Middle(Middle),
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+#[error(chain)]
+struct Coded {
+    code: u8,
+    #[source]
+    root: Root,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Display, Error)]
+#[display_from(Debug)]
+#[error(chain)]
+struct Cycle(#[source] &'static Cycle);
+
+#[cfg(not(MSRV))]
+static CYCLE: Cycle = Cycle(&CYCLE);
+
+#[cfg(not(MSRV))]
+impl std::fmt::Debug for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("cycle")
+    }
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+enum RpcError {
+    #[error("invalid request")]
+    #[error_code(-32600)]
+    InvalidRequest,
+    #[error("method {0} not found")]
+    #[error_code(-32601)]
+    MethodNotFound(String),
+    #[error(transparent)]
+    #[error_code(delegate)]
+    #[derive_from]
+    Storage(StorageError),
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+enum StorageError {
+    #[error("disk full")]
+    #[error_code = 100]
+    DiskFull,
+    #[error("corrupted")]
+    #[error_code = 101]
+    Corrupted { _block: u64 },
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+enum BoundaryError {
+    #[error("lowest")]
+    #[error_code(-2147483648)]
+    Lowest,
+    #[error("highest")]
+    #[error_code = 2147483647]
+    Highest,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+#[io_kind(InvalidData)]
+enum DecodeError {
+    #[error("unexpected end of input")]
+    #[io_kind(UnexpectedEof)]
+    Eof,
+    #[error("bad magic {0:#x}")]
+    Magic(u32),
+    #[error("unsupported")]
+    Unsupported,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+#[io_kind(PermissionDenied)]
+struct Denied;
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+enum ConfigError {
+    #[error("can't read {path}: {source}")]
+    Read {
+        path: &'static str,
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid port {0:#x}")]
+    Port(u16),
+    #[error(transparent)]
+    #[derive_from]
+    Fmt(std::fmt::Error),
+    #[error("missing {{key}}")]
+    #[derive_from(())]
+    Missing,
+    #[error("nested")]
+    #[derive_from]
+    Nested(ReadError),
+}
+
+#[cfg(not(MSRV))]
+trait Failure: std::fmt::Debug + std::fmt::Display {
+    fn source(&self) -> Option<&(dyn Failure + 'static)> {
+        None
+    }
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display)]
+#[display_from(Debug)]
+struct Timeout;
+
+#[cfg(not(MSRV))]
+impl Failure for Timeout {}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Error)]
+#[error(trait = Failure, chain)]
+enum PluginError {
+    #[error("plugin {0} crashed")]
+    Crashed(u8),
+    #[error("plugin stalled")]
+    Stalled(#[source] Timeout),
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+#[error(trait = Failure)]
+struct Host {
+    #[source]
+    plugin: PluginError,
+}
+
+#[cfg(not(MSRV))]
+#[derive(Debug, Display, Error)]
+#[display_from(Debug)]
+#[error(trait = Failure)]
+struct ExitCode(u8);
+
+#[derive(Add, Sub, Mul, Div, Rem, Debug, PartialEq)]
+struct Amount(u64);
+
+#[derive(Add, Sub, Neg, Debug, PartialEq)]
+struct Height(i64);
+
+#[derive(Add, Sub, Mul, Div, Rem, Neg, Debug, PartialEq)]
+struct Weight<U> {
+    #[wrap]
+    grams: f64,
+    unit: PhantomData<U>,
+}
+
+#[derive(Add, BitOr, Sum, InnerPartialEq, Debug, PartialEq)]
+struct Tally<'a> {
+    #[wrap]
+    count: u64,
+    label: &'a str,
+}
+
+#[derive(AddAssign, SubAssign, MulAssign, DivAssign, RemAssign, Debug, PartialEq)]
+struct Counter {
+    #[wrap]
+    hits: u32,
+    name: &'static str,
+}
+
+#[derive(
+    BitAndAssign, BitOrAssign, BitXorAssign, ShlAssign, ShrAssign, Clone, Copy, Debug, PartialEq,
+)]
+struct Mask(u8);
+
+#[derive(Ops, Sum, Debug, PartialEq)]
+#[ops(Mul<Inner> -> Self, Div<Inner> -> Self, Add<Self> -> Self)]
+struct Duration(u64);
+
+#[derive(Ops, Debug, PartialEq)]
+#[ops(Div<Self> -> Inner, Sub<Self> -> Self, Shl<Inner> -> Inner)]
+struct Distance(u32);
+
+#[derive(Ops, Debug, PartialEq)]
+#[ops(Mul<Inner> -> Self, Add<Self> -> Self)]
+struct Quantity<U> {
+    #[wrap]
+    amount: u64,
+    unit: PhantomData<U>,
+}
+
+#[derive(IntegerOps, Clone, Copy, Debug, PartialEq)]
+struct Sats(u64);
+
+#[cfg(not(MSRV))]
+#[derive(IntegerOps, Clone, Copy, Debug, PartialEq)]
+#[integer_ops(consts)]
+struct Delta<U> {
+    #[wrap]
+    value: i8,
+    unit: PhantomData<U>,
+}
+
+#[derive(Flags, BitAnd, BitOr, BitXor, Not, Shl, Shr, LowerHex, Clone, Copy, PartialEq)]
+#[flags(NETWORK = 1, GETUTXO = 1 << 1, BLOOM = 4, WITNESS = 8, NONE = 0)]
+struct ServiceFlags(u64);
+
+#[derive(Flags, IntegerOps, Clone, Copy, PartialEq)]
+#[flags(LOW = 1, HIGH = 1 << 1)]
+struct Priority(u8);
+
+#[cfg(not(MSRV))]
+#[derive(Flags, BitOr)]
+#[flags(READ = 0b01, WRITE = 0b10, EXECUTE = 1 << 2)]
+struct Access<T> {
+    #[wrap]
+    bits: u8,
+    target: PhantomData<T>,
+}
+
+#[derive(Sum, Product, Add, Debug, PartialEq)]
+#[fold(inner)]
+struct Fee(u64);
+
+#[derive(Sum, Product, Debug, PartialEq)]
+struct Scale<U> {
+    #[wrap]
+    factor: f64,
+    unit: PhantomData<U>,
+}
+
+#[cfg(not(MSRV))]
+#[derive(ByteOrder, Debug, PartialEq)]
+struct BlockHeight(u32);
+
+#[cfg(not(MSRV))]
+#[derive(ByteOrder, Debug, PartialEq)]
+#[byte_order(be)]
+struct Port {
+    #[wrap]
+    number: u16,
+    name: Option<&'static str>,
+}
+
+#[cfg(not(MSRV))]
+#[derive(ByteOrder, Flags, Clone, Copy, PartialEq)]
+#[byte_order(be)]
+#[flags(SYN = 1 << 1, ACK = 1 << 4)]
+struct TcpFlags(u16);
+
+#[cfg(not(MSRV))]
+#[derive(Decimal, Debug, PartialEq)]
+#[decimal(scale = 8)]
+struct Btc(u64);
+
+#[cfg(not(MSRV))]
+#[derive(Decimal, Debug, PartialEq)]
+#[decimal(scale = 2, trim)]
+struct Cents(i64);
+
+#[cfg(not(MSRV))]
+#[derive(Decimal, Debug, PartialEq)]
+#[decimal(scale = 0)]
+struct Whole(u128);
+
+#[derive(WrapperPartialEq, WrapperEq, WrapperPartialOrd, WrapperOrd, WrapperHash, AsRef)]
+struct Cached {
+    #[wrap]
+    key: [u8; 4],
+    memo: std::cell::Cell<Option<u64>>,
+}
+
+fn lowercase(name: &str) -> String {
+    name.to_lowercase()
+}
+
+#[derive(WrapperPartialEq, WrapperEq, WrapperHash, Debug)]
+struct Header {
+    #[wrap(eq_by = lowercase)]
+    name: &'static str,
+    value: &'static str,
+}
+
+fn magnitude(value: &f64) -> f64 {
+    value.abs()
+}
+
+#[derive(WrapperPartialEq, WrapperPartialOrd)]
+struct Score {
+    #[wrap(eq_by = magnitude)]
+    value: f64,
+    player: &'static str,
+}
+
+#[derive(InnerPartialEq, InnerPartialOrd)]
+struct Satoshis(u64);
+
+#[derive(InnerPartialEq)]
+struct Digest([u8; 4]);
+
+#[derive(InnerPartialEq, InnerPartialOrd)]
+struct Name {
+    #[wrap]
+    value: String,
+    interned: bool,
+}
+
+#[derive(InnerPartialEq)]
+struct Payload(Vec<u8>);
+
+#[derive(InnerPartialEq)]
+struct Frozen(Box<[u8]>);
+
+#[derive(ArrayDefault, ArrayDebug, ArrayHash, ArrayClone)]
+struct Pubkey([u8; 65]);
+
+#[derive(ArrayDefault, ArrayDebug, ArrayHash, ArrayClone)]
+struct Tagged {
+    tag: &'static str,
+    #[wrap]
+    words: [u16; 40],
+}
+
+#[derive(ArrayDefault, ArrayClone)]
+struct Samples64<T>([T; 64]);
+
+#[derive(WrapperDebug)]
+#[debug(hex)]
+struct Txid([u8; 32]);
+
+#[derive(WrapperDebug, LowerHexIter)]
+#[debug(hex)]
+#[hex(separator = ":")]
+struct Mac48([u8; 6]);
+
+#[derive(WrapperDebug, LowerHexIter)]
+#[debug(hex)]
+#[hex(groups = [4, 2, 2, 2, 6], separator = "-")]
+struct RequestId([u8; 16]);
+
+#[derive(WrapperDebug)]
+#[debug(transparent)]
+struct Label {
+    #[wrap]
+    text: &'static str,
+    hits: u32,
+}
+
+#[derive(WrapperDebug, Display, LowerHex)]
+#[debug(display)]
+#[display_from(LowerHex)]
+struct Nonce(u32);
+
+#[derive(Redacted)]
+struct ApiToken(String);
+
+#[derive(Redacted)]
+#[redacted(placeholder = "***", fingerprint = "hex", display)]
+struct SecretKey([u8; 32]);
+
+#[derive(Redacted)]
+#[redacted(fingerprint = "len")]
+struct Seed {
+    #[wrap]
+    bytes: Vec<u8>,
+    birthday: u32,
+}
+
+#[derive(Redacted, LowerHex, UpperHex, Octal, Binary)]
+#[redacted(allow_reveal)]
+struct Salt(u64);
+
+#[derive(LowerHexIter, UpperHexIter, OctalIter, BinaryIter, FromHexIter, Debug, PartialEq)]
+struct Limbs([u32; 2]);
+
+#[derive(LowerHexIter, FromHexIter, Debug, PartialEq)]
+#[hex(separator = ":")]
+struct Offsets([i16; 2]);
+
+#[derive(LowerHexIter)]
+#[hex(separator = " ")]
+struct Samples(Vec<u16>);
+
 //#[derive(AsRef)]
 //struct Fail1 {
 //    a: (),
@@ -129,6 +643,678 @@ fn test_error() {
     );
 }
 
+fn test_error_source() {
+    #[cfg(not(MSRV))]
+    {
+        let read = ReadError {
+            path: "/tmp".into(),
+            cause: io::Error::new(io::ErrorKind::NotFound, "missing"),
+        };
+        assert_eq!(read.source().unwrap().to_string(), "missing");
+
+        let wrapped = Wrapped {
+            inner: read,
+            context: "loading",
+        };
+        let source = wrapped.source().unwrap();
+        assert!(source.downcast_ref::<ReadError>().is_some());
+        assert_eq!(source.source().unwrap().to_string(), "missing");
+
+        assert!(Maybe(None).source().is_none());
+        let maybe = Maybe(Some(io::Error::new(io::ErrorKind::InvalidData, "other")));
+        assert_eq!(maybe.source().unwrap().to_string(), "other");
+
+        let boxed = Boxed {
+            cause: Box::new(io::Error::new(io::ErrorKind::InvalidData, "boxed")),
+        };
+        assert_eq!(boxed.source().unwrap().to_string(), "boxed");
+        assert!(One::default().source().is_none());
+    }
+}
+
+fn test_error_enum() {
+    #[cfg(not(MSRV))]
+    {
+        let read = ConfigError::Read {
+            path: "/etc/app",
+            source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        };
+        assert_eq!(read.to_string(), "can't read /etc/app: not found");
+        assert_eq!(read.source().unwrap().to_string(), "not found");
+
+        let port = ConfigError::Port(80);
+        assert_eq!(port.to_string(), "invalid port 0x50");
+        assert!(port.source().is_none());
+
+        let fmt = ConfigError::from(std::fmt::Error);
+        assert_eq!(fmt.to_string(), std::fmt::Error.to_string());
+        assert!(fmt.source().is_none());
+
+        let missing = ConfigError::from(());
+        assert_eq!(missing.to_string(), "missing {key}");
+
+        let nested = ConfigError::from(ReadError {
+            path: "/tmp".into(),
+            cause: io::Error::new(io::ErrorKind::NotFound, "inner"),
+        });
+        assert_eq!(nested.to_string(), "nested");
+        let source = nested.source().unwrap();
+        assert!(source.downcast_ref::<ReadError>().is_some());
+        assert_eq!(source.source().unwrap().to_string(), "inner");
+    }
+}
+
+fn test_error_transparent() {
+    #[cfg(not(MSRV))]
+    {
+        let opaque = Opaque(ConfigError::Read {
+            path: "/etc/app",
+            source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        });
+        assert_eq!(opaque.to_string(), "can't read /etc/app: not found");
+        assert_eq!(opaque.source().unwrap().to_string(), "not found");
+        assert!(format!("{:?}", opaque).starts_with("Opaque(Read {"));
+
+        let read = ReadError {
+            path: "/tmp".into(),
+            cause: io::Error::new(io::ErrorKind::NotFound, "inner"),
+        };
+        let debug = format!("{:?}", read);
+        let display = read.to_string();
+        let invisible = Invisible {
+            inner: read,
+            _retries: 3,
+        };
+        assert_eq!(format!("{:?}", invisible), debug);
+        assert_eq!(invisible.to_string(), display);
+        assert_eq!(invisible.source().unwrap().to_string(), "inner");
+    }
+}
+
+fn test_error_chain() {
+    #[cfg(not(MSRV))]
+    {
+        let middle = Middle::from(Root::Cause);
+        assert_eq!(middle.to_string(), "middle");
+        assert_eq!(format!("{:#}", middle), "middle: root cause");
+
+        let transparent = Transparent(Middle::from(Root::Cause));
+        assert_eq!(transparent.to_string(), "middle");
+        assert_eq!(format!("{:#}", transparent), "middle: root cause");
+
+        let app = App::from(Middle::from(Root::Cause));
+        assert_eq!(app.to_string(), "outer");
+        assert_eq!(format!("{:#}", app), "outer -> middle -> root cause");
+
+        let forwarded = Forwarded::Middle(Middle::from(Root::Cause));
+        assert_eq!(forwarded.to_string(), "middle");
+        assert_eq!(format!("{:#}", forwarded), "middle: root cause");
+
+        let coded = Coded {
+            code: 1,
+            root: Root::Cause,
+        };
+        assert_eq!(coded.to_string(), "Coded { code: 1, root: Cause }");
+        assert_eq!(
+            format!("{:#}", coded),
+            "Coded { code: 1, root: Cause }: root cause"
+        );
+
+        let cycle = format!("{:#}", CYCLE);
+        assert!(cycle.ends_with(": cycle: ..."));
+        assert_eq!(cycle.matches("cycle").count(), 33);
+    }
+}
+
+fn test_error_codes() {
+    #[cfg(not(MSRV))]
+    {
+        assert_eq!(RpcError::InvalidRequest.code(), -32600);
+        assert_eq!(RpcError::MethodNotFound("foo".into()).code(), -32601);
+        assert_eq!(RpcError::from(StorageError::DiskFull).code(), 100);
+        assert_eq!(
+            RpcError::from(StorageError::Corrupted { _block: 5 }).code(),
+            101
+        );
+        assert_eq!(
+            RpcError::CODES,
+            &[(-32600, "InvalidRequest"), (-32601, "MethodNotFound")]
+        );
+        assert_eq!(
+            StorageError::CODES,
+            &[(100, "DiskFull"), (101, "Corrupted")]
+        );
+        assert_eq!(BoundaryError::Lowest.code(), i32::MIN);
+        assert_eq!(BoundaryError::Highest.code(), i32::MAX);
+    }
+}
+
+fn test_error_io_kind() {
+    #[cfg(not(MSRV))]
+    {
+        let eof = io::Error::from(DecodeError::Eof);
+        assert_eq!(eof.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(eof.to_string(), "unexpected end of input");
+
+        let magic: io::Error = DecodeError::Magic(0xcafe).into();
+        assert_eq!(magic.kind(), io::ErrorKind::InvalidData);
+        match magic.into_inner().unwrap().downcast::<DecodeError>() {
+            Ok(inner) => match *inner {
+                DecodeError::Magic(magic) => assert_eq!(magic, 0xcafe),
+                _ => panic!("wrong variant"),
+            },
+            Err(_) => panic!("the payload should be a DecodeError"),
+        }
+        assert_eq!(
+            io::Error::from(DecodeError::Unsupported).kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let denied = io::Error::from(Denied);
+        assert_eq!(denied.kind(), io::ErrorKind::PermissionDenied);
+        assert!(denied.get_ref().unwrap().is::<Denied>());
+    }
+}
+
+fn test_error_trait() {
+    #[cfg(not(MSRV))]
+    {
+        let crashed = PluginError::Crashed(3);
+        assert_eq!(crashed.to_string(), "plugin 3 crashed");
+        assert!(Failure::source(&crashed).is_none());
+
+        let stalled = PluginError::Stalled(Timeout);
+        assert_eq!(format!("{:#}", stalled), "plugin stalled: Timeout");
+        assert_eq!(Failure::source(&stalled).unwrap().to_string(), "Timeout");
+
+        let host = Host { plugin: stalled };
+        assert_eq!(
+            Failure::source(&host).unwrap().to_string(),
+            "plugin stalled"
+        );
+
+        let exit = ExitCode(2);
+        assert_eq!(exit.to_string(), "ExitCode(2)");
+        assert!(Failure::source(&exit).is_none());
+    }
+}
+
+fn test_arithmetic() {
+    let a = Amount(40);
+    let b = Amount(6);
+    assert_eq!(&a + &b, Amount(46));
+    assert_eq!(&a - &b, Amount(34));
+    assert_eq!(&a * &b, Amount(240));
+    assert_eq!(&a / &b, Amount(6));
+    assert_eq!(&a % &b, Amount(4));
+    assert_eq!(&a + Amount(2), Amount(42));
+    assert_eq!(Amount(2) * &b, Amount(12));
+    assert_eq!(a - b, Amount(34));
+
+    let low = Height(-5);
+    assert_eq!(-&low, Height(5));
+    assert_eq!(-low, Height(5));
+    assert_eq!(Height(3) - Height(10), Height(-7));
+
+    let kg: Weight<()> = Weight {
+        grams: 1000.0,
+        unit: PhantomData,
+    };
+    let half = Weight {
+        grams: 500.0,
+        unit: PhantomData,
+    };
+    assert_eq!((&kg + &half).grams, 1500.0);
+    assert_eq!((&kg / &half).grams, 2.0);
+    assert_eq!((-kg).unit, PhantomData);
+
+    let votes = Tally {
+        count: 3,
+        label: "votes",
+    };
+    let more = &votes
+        + &Tally {
+            count: 4,
+            label: "",
+        };
+    assert_eq!(more.label, "votes");
+    assert_eq!(more, 7);
+    assert_eq!((&votes | &more).count, 7);
+    assert_eq!(vec![&votes, &more].into_iter().sum::<Tally>().count, 10);
+}
+
+fn test_assign_ops() {
+    let mut counter = Counter {
+        hits: 10,
+        name: "requests",
+    };
+    counter += 5;
+    counter -= Counter { hits: 3, name: "" };
+    counter *= 4;
+    counter /= Counter { hits: 6, name: "" };
+    assert_eq!(counter.hits, 8);
+    counter %= 5;
+    assert_eq!(
+        counter,
+        Counter {
+            hits: 3,
+            name: "requests"
+        }
+    );
+
+    let mut mask = Mask(0b1100);
+    mask |= Mask(0b0011);
+    assert_eq!(mask, Mask(0b1111));
+    mask &= 0b0110;
+    assert_eq!(mask, Mask(0b0110));
+    mask ^= Mask(0b0101);
+    assert_eq!(mask, Mask(0b0011));
+    mask <<= 4;
+    assert_eq!(mask, Mask(0b0011_0000));
+    mask >>= Mask(2);
+    assert_eq!(mask, Mask(0b1100));
+}
+
+fn test_scalar_ops() {
+    assert_eq!(Duration(30) * 4, Duration(120));
+    assert_eq!(Duration(30) / 4, Duration(7));
+    assert_eq!(Duration(30) + Duration(12), Duration(42));
+
+    let ratio: u32 = Distance(300) / Distance(100);
+    assert_eq!(ratio, 3);
+    assert_eq!(Distance(300) - Distance(100), Distance(200));
+    assert_eq!(Distance(3) << 2, 12u32);
+
+    let dozen = Quantity::<()> {
+        amount: 12,
+        unit: PhantomData,
+    };
+    let double = dozen * 2
+        + Quantity {
+            amount: 1,
+            unit: PhantomData,
+        };
+    assert_eq!(double.amount, 25);
+}
+
+fn test_integer_ops() {
+    assert_eq!(Sats(5).checked_add(Sats(7)), Some(Sats(12)));
+    assert_eq!(Sats(5).checked_sub(Sats(7)), None);
+    assert_eq!(Sats(1 << 63).checked_mul(Sats(2)), None);
+    assert_eq!(Sats(5).saturating_sub(Sats(7)), Sats(0));
+    assert_eq!(Sats(!0).saturating_add(Sats(1)), Sats(!0));
+    assert_eq!(Sats(!0).wrapping_add(Sats(2)), Sats(1));
+    assert_eq!(Sats(3).wrapping_mul(Sats(4)), Sats(12));
+
+    #[cfg(not(MSRV))]
+    {
+        let delta: Delta<()> = Delta::MAX;
+        assert_eq!(delta.value, 127);
+        assert_eq!(Delta::<()>::MIN.value, -128);
+        assert_eq!(delta.checked_add(Delta::ZERO), Some(delta));
+        assert_eq!(delta.wrapping_add(delta).value, -2);
+        assert_eq!(Delta::MIN.saturating_sub(delta), Delta::MIN);
+    }
+
+    assert_eq!(Priority::LOW.checked_add(Priority::HIGH), Some(Priority(3)));
+    assert_eq!(Priority::HIGH.wrapping_mul(Priority(128)), Priority(0));
+}
+
+fn test_flags() {
+    let mut flags = ServiceFlags::NETWORK | ServiceFlags::WITNESS;
+    assert!(flags.contains(ServiceFlags::WITNESS));
+    assert!(flags.contains(ServiceFlags::NONE));
+    assert!(!flags.contains(ServiceFlags::BLOOM | ServiceFlags::WITNESS));
+    assert_eq!(flags.to_string(), "NETWORK | WITNESS");
+    assert_eq!(format!("{:?}", flags), "ServiceFlags(NETWORK | WITNESS)");
+
+    flags.insert(ServiceFlags::GETUTXO | ServiceFlags(0x400));
+    flags.remove(ServiceFlags::NETWORK);
+    let names: Vec<_> = flags.iter_names().map(|(name, _)| name).collect();
+    assert_eq!(names, ["GETUTXO", "WITNESS"]);
+    assert_eq!(flags.to_string(), "GETUTXO | WITNESS | 0x400");
+    assert_eq!(ServiceFlags(0x30).to_string(), "0x30");
+    assert_eq!(ServiceFlags::NONE.to_string(), "0x0");
+
+    assert_eq!(flags & ServiceFlags::WITNESS, ServiceFlags::WITNESS);
+    assert_eq!(flags ^ ServiceFlags::WITNESS, ServiceFlags(0x402));
+    assert_eq!(format!("{:x}", !ServiceFlags(0) << 60), "f000000000000000");
+    assert_eq!(&ServiceFlags::WITNESS >> 3, ServiceFlags::NETWORK);
+
+    #[cfg(not(MSRV))]
+    {
+        let access: Access<()> = Access::READ | Access::WRITE;
+        assert_eq!(format!("{:?}", access), "Access(READ | WRITE)");
+        let set: Vec<_> = access.iter_names().map(|(_, flag)| flag.bits).collect();
+        assert_eq!(set, [1, 2]);
+        let all: Access<()> = access | Access::EXECUTE;
+        assert_eq!(all.to_string(), "READ | WRITE | EXECUTE");
+    }
+}
+
+fn test_sum_product() {
+    let fees = vec![Fee(10), Fee(20), Fee(12)];
+    let total: Fee = fees.iter().sum();
+    assert_eq!(total, Fee(42));
+    assert_eq!(total, fees.iter().fold(Fee(0), |acc, fee| acc + fee));
+    assert_eq!(fees.iter().product::<Fee>(), Fee(2400));
+    assert_eq!(fees.into_iter().sum::<Fee>(), Fee(42));
+    assert_eq!(vec![3u64, 4].into_iter().sum::<Fee>(), Fee(7));
+    assert_eq!(vec![3u64, 4].into_iter().product::<Fee>(), Fee(12));
+    assert_eq!(Vec::<Fee>::new().into_iter().product::<Fee>(), Fee(1));
+    assert_eq!(
+        vec![Duration(5), Duration(7)].into_iter().sum::<Duration>(),
+        Duration(12)
+    );
+
+    let scales = [
+        Scale::<()> {
+            factor: 2.0,
+            unit: PhantomData,
+        },
+        Scale {
+            factor: 0.25,
+            unit: PhantomData,
+        },
+    ];
+    assert_eq!(scales.iter().product::<Scale<()>>().factor, 0.5);
+    assert_eq!(scales.iter().sum::<Scale<()>>().factor, 2.25);
+}
+
+fn test_byte_order() {
+    #[cfg(not(MSRV))]
+    {
+        use std::convert::TryFrom;
+
+        let height = BlockHeight(0x0102_0304);
+        assert_eq!(height.to_le_bytes(), [4, 3, 2, 1]);
+        assert_eq!(height.to_be_bytes(), [1, 2, 3, 4]);
+        assert_eq!(height.to_ne_bytes(), 0x0102_0304u32.to_ne_bytes());
+        assert_eq!(BlockHeight::from_le_bytes([4, 3, 2, 1]), height);
+        assert_eq!(BlockHeight::from_be_bytes([1, 2, 3, 4]), height);
+        assert_eq!(
+            BlockHeight::from_ne_bytes(0x0102_0304u32.to_ne_bytes()),
+            height
+        );
+        assert_eq!(BlockHeight::try_from(&[4u8, 3, 2, 1][..]).unwrap(), height);
+        assert!(BlockHeight::try_from(&[1u8, 2, 3][..]).is_err());
+
+        let port = Port::try_from(&[0x1f, 0x90][..]).unwrap();
+        assert_eq!(
+            port,
+            Port {
+                number: 8080,
+                name: None
+            }
+        );
+        assert_eq!(port.to_le_bytes(), [0x90, 0x1f]);
+
+        let tcp = TcpFlags::try_from(&[0x00, 0x12][..]).unwrap();
+        assert_eq!(tcp.to_string(), "SYN | ACK");
+        assert_eq!(tcp.to_be_bytes(), [0x00, 0x12]);
+    }
+}
+
+fn test_decimal() {
+    #[cfg(not(MSRV))]
+    {
+        assert_eq!(Btc(123_450_000).to_string(), "1.23450000");
+        assert_eq!(Btc(5).to_string(), "0.00000005");
+        assert_eq!(format!("{:.3}", Btc(123_450_000)), "1.235");
+        assert_eq!(format!("{:.0}", Btc(199_999_999)), "2");
+        assert_eq!(format!("{:.10}", Btc(100_000_000)), "1.0000000000");
+        assert_eq!(format!("{:>12.2}", Btc(50_000_000)), "        0.50");
+        assert_eq!(format!("{:+}", Btc(1)), "+0.00000001");
+        assert_eq!("0.5".parse(), Ok(Btc(50_000_000)));
+        assert_eq!("21000000".parse(), Ok(Btc(2_100_000_000_000_000)));
+        assert_eq!("+1.00000001".parse(), Ok(Btc(100_000_001)));
+        assert_eq!(
+            "1.000000001".parse::<Btc>(),
+            Err(ParseBtcError::TooManyDecimals)
+        );
+        assert_eq!("-1".parse::<Btc>(), Err(ParseBtcError::InvalidDigit(0)));
+        assert_eq!("1.2x".parse::<Btc>(), Err(ParseBtcError::InvalidDigit(3)));
+        assert_eq!("1.".parse::<Btc>(), Err(ParseBtcError::Empty));
+        assert_eq!(".5".parse::<Btc>(), Err(ParseBtcError::Empty));
+        assert_eq!(
+            "184467440737.09551616".parse::<Btc>(),
+            Err(ParseBtcError::Overflow)
+        );
+        assert_eq!("184467440737.09551615".parse(), Ok(Btc(u64::MAX)));
+
+        assert_eq!(Cents(-1250).to_string(), "-12.5");
+        assert_eq!(Cents(300).to_string(), "3");
+        assert_eq!(Cents(-5).to_string(), "-0.05");
+        assert_eq!(format!("{:.2}", Cents(300)), "3.00");
+        assert_eq!(format!("{:08.1}", Cents(-1255)), "-00012.6");
+        assert_eq!(Cents(i64::MIN).to_string(), "-92233720368547758.08");
+        assert_eq!("-92233720368547758.08".parse(), Ok(Cents(i64::MIN)));
+        assert_eq!(
+            "92233720368547758.08".parse::<Cents>(),
+            Err(ParseCentsError::Overflow)
+        );
+        assert_eq!("-0.5".parse(), Ok(Cents(-50)));
+        assert_eq!("".parse::<Cents>(), Err(ParseCentsError::Empty));
+
+        assert_eq!(Whole(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(format!("{:.2}", Whole(7)), "7.00");
+        assert_eq!("42".parse(), Ok(Whole(42)));
+        assert_eq!(
+            "4.2".parse::<Whole>(),
+            Err(ParseWholeError::TooManyDecimals)
+        );
+    }
+}
+
+fn test_compare_wrapped() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    let a = Cached {
+        key: [1, 2, 3, 4],
+        memo: std::cell::Cell::new(None),
+    };
+    let b = Cached {
+        key: [1, 2, 3, 4],
+        memo: std::cell::Cell::new(Some(7)),
+    };
+    let c = Cached {
+        key: [1, 2, 3, 5],
+        memo: std::cell::Cell::new(None),
+    };
+    assert!(a == b);
+    assert!(a < c && c > b);
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+    assert_eq!(a.as_ref(), &[1, 2, 3, 4]);
+    let hash = |cached: &Cached| {
+        let mut hasher = DefaultHasher::new();
+        cached.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(hash(&a), hash(&c));
+
+    let headers: HashSet<_> = vec![
+        Header {
+            name: "Content-Type",
+            value: "text/plain",
+        },
+        Header {
+            name: "content-type",
+            value: "text/html",
+        },
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(headers.len(), 1);
+
+    let alice = Score {
+        value: -3.0,
+        player: "alice",
+    };
+    let bob = Score {
+        value: 2.0,
+        player: "bob",
+    };
+    assert!(alice > bob);
+    assert!(alice.player < bob.player);
+}
+
+fn test_compare_inner() {
+    let amount = Satoshis(5000);
+    assert!(amount == 5000u64);
+    assert!(5000u64 == amount);
+    assert!(amount < 6000u64 && 4000u64 < amount);
+
+    let digest = Digest([0; 4]);
+    assert!(digest == [0u8; 4]);
+    assert!([0u8; 4] == digest);
+    let zeros: &[u8] = &[0; 4];
+    let short: &[u8] = &[0; 2];
+    assert!(digest == *zeros);
+    assert!(zeros == digest);
+    assert!(digest != short);
+
+    let name = Name {
+        value: "foo".to_string(),
+        interned: false,
+    };
+    let owned = String::from("foo");
+    assert!(name == owned);
+    assert!(name == "foo");
+    assert!("foo" == name);
+    assert!(name == *"foo");
+    assert!(name > "bar" && "zoo" > name);
+    assert!(!name.interned);
+
+    let payload = Payload(b"abc".to_vec());
+    let abc: &[u8] = b"abc";
+    let abd: &[u8] = b"abd";
+    assert!(payload == abc);
+    assert!(payload == abc.to_vec());
+    assert!(abd != payload);
+
+    let frozen = Frozen(b"abc".to_vec().into_boxed_slice());
+    assert!(frozen == abc);
+    assert!(*abc == frozen);
+}
+
+fn test_array_traits() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let mut key = Pubkey::default();
+    assert!(key.0.iter().all(|&b| b == 0));
+    key.0[64] = 7;
+    let copy = key.clone();
+    assert_eq!(copy.0[..], key.0[..]);
+    assert_eq!(hash(&copy), hash(&&key.0[..]));
+    let debug = format!("{:?}", key);
+    assert!(debug.starts_with("Pubkey([0, 0, ") && debug.ends_with(", 7])"));
+
+    let mut tagged = Tagged::default();
+    assert_eq!(tagged.tag, "");
+    tagged.tag = "words";
+    tagged.words[0] = 1;
+    let cloned = tagged.clone();
+    assert_eq!(cloned.words[0], 1);
+    assert_eq!(hash(&cloned), hash(&tagged));
+    assert_eq!(hash(&cloned), hash(&("words", &tagged.words[..])));
+    let debug = format!("{:?}", cloned);
+    assert!(debug.starts_with("Tagged { tag: \"words\", words: [1, 0, "));
+
+    let mut samples = Samples64::<f32>::default();
+    samples.0[63] = 0.5;
+    assert_eq!(samples.clone().0[..], samples.0[..]);
+}
+
+fn test_debug_modes() {
+    let mut txid = Txid([0; 32]);
+    txid.0[0] = 0xab;
+    txid.0[31] = 0x01;
+    assert_eq!(
+        format!("{:?}", txid),
+        "Txid(ab00000000000000000000000000000000000000000000000000000000000001)"
+    );
+    let mac = Mac48([0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6]);
+    assert_eq!(format!("{:?}", mac), "Mac48(00:1b:63:84:45:e6)");
+    assert_eq!(format!("{:x}", mac), "00:1b:63:84:45:e6");
+    assert_eq!(
+        format!("{:?}", RequestId([0xab; 16])),
+        "RequestId(abababab-abab-abab-abab-abababababab)"
+    );
+
+    let label = Label {
+        text: "inbox",
+        hits: 3,
+    };
+    assert_eq!(format!("{:?}", label), "\"inbox\"");
+    assert_eq!(label.hits, 3);
+    assert_eq!(format!("{:?}", Some(Nonce(0xbeef))), "Some(beef)");
+}
+
+fn test_redacted() {
+    let token = ApiToken("hunter2".to_string());
+    assert_eq!(format!("{:?}", token), "ApiToken(<redacted>)");
+    assert_eq!(token.0.len(), 7);
+
+    let mut key = SecretKey([0x11; 32]);
+    key.0[0] = 0xde;
+    assert_eq!(format!("{:?}", key), "SecretKey(*** de111111..)");
+    assert_eq!(key.to_string(), "*** de111111..");
+
+    let seed = Seed {
+        bytes: vec![7; 64],
+        birthday: 2009,
+    };
+    assert_eq!(format!("{:?}", seed), "Seed(<redacted>, 64 bytes)");
+    assert_eq!(seed.birthday, 2009);
+
+    assert_eq!(
+        format!("{:?} {:x}", Salt(0xff), Salt(0xff)),
+        "Salt(<redacted>) ff"
+    );
+    assert_eq!(
+        format!("{:X} {:o} {:b}", Salt(0xff), Salt(0o17), Salt(0b101)),
+        "FF 17 101"
+    );
+}
+
+fn test_iter_wide_elements() {
+    let limbs = Limbs([0x11, 0xdead_beef]);
+    assert_eq!(format!("{:x}", limbs), "00000011deadbeef");
+    assert_eq!(format!("{:X}", limbs), "00000011DEADBEEF");
+    assert_eq!(format!("{:#.10x}", limbs), "0x00000011de");
+    assert_eq!(format!("{:>20x}", limbs), "    00000011deadbeef");
+    assert_eq!(
+        format!("{:b}", Limbs([1, 2])),
+        format!("{:032b}{:032b}", 1, 2)
+    );
+    assert_eq!(format!("{:o}", Limbs([8, 0])), "000000000010000000000000");
+    assert_eq!("00000011deadbeef".parse::<Limbs>(), Ok(limbs));
+    assert_eq!(
+        "00000011deadbe".parse::<Limbs>(),
+        Err(ParseLimbsError::InvalidLength)
+    );
+
+    let offsets = Offsets([-1, 0x1234]);
+    let fmt = format!("{:x}", offsets);
+    assert_eq!(fmt, "ffff:1234");
+    assert_eq!(fmt.parse::<Offsets>(), Ok(offsets));
+
+    let samples = Samples(vec![0x1, 0xabc, 0xffff]);
+    assert_eq!(format!("{:x}", samples), "0001 0abc ffff");
+    assert_eq!(format!("{:.6x}", samples), "0001 0a");
+    assert_eq!(format!("{:x}", Samples(Vec::new())), "");
+}
+
 fn test_index_heap() {
     let a = vec![1, 2, 3, 4, 5, 6, 7].into_boxed_slice();
     let heap = Heap(a);
@@ -150,6 +1336,162 @@ fn test_lowerhex() {
     assert_eq!(fmt, "0xff");
 }
 
+fn test_lowerhex_iter_flags() {
+    let heap = Heap(vec![0x0a, 0xbc, 0x01].into_boxed_slice());
+    assert_eq!(format!("{:x}", heap), "0abc01");
+    assert_eq!(format!("{:#x}", heap), "0x0abc01");
+    assert_eq!(format!("{:>10x}", heap), "    0abc01");
+    assert_eq!(format!("{:<10x}|", heap), "0abc01    |");
+    assert_eq!(format!("{:*^10x}", heap), "**0abc01**");
+    assert_eq!(format!("{:#010x}", heap), "0x000abc01");
+    assert_eq!(format!("{:.3x}", heap), "0ab");
+    assert_eq!(format!("{:#8.4x}", heap), "  0x0abc");
+    assert_eq!(format!("{:.10x}", heap), "0abc01");
+}
+
+fn test_hex_separators() {
+    let mac = Mac([0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7]);
+    assert_eq!(mac.to_string(), "00:1b:44:11:3a:b7");
+    assert_eq!(format!("{:.5x}", mac), "00:1b:4");
+    assert_eq!(format!("{:>20x}", mac), "   00:1b:44:11:3a:b7");
+    assert_eq!("00:1b:44:11:3a:b7".parse::<Mac>(), Ok(mac));
+    assert_eq!("00:1B:44:11:3A:B7".parse::<Mac>().unwrap().0[1], 0x1b);
+    assert_eq!(
+        "00:1b:44:11:3a".parse::<Mac>(),
+        Err(ParseMacError::InvalidLength)
+    );
+    assert_eq!(
+        "00-1b:44:11:3a:b7".parse::<Mac>(),
+        Err(ParseMacError::InvalidSeparator(2))
+    );
+    assert_eq!(
+        "00:1g:44:11:3a:b7".parse::<Mac>(),
+        Err(ParseMacError::InvalidDigit(4))
+    );
+    assert_eq!(
+        "00:1b:44:11:3a:b7:".parse::<Mac>(),
+        Err(ParseMacError::InvalidLength)
+    );
+
+    let uuid = Uuid([
+        0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x14, 0x17, 0x40,
+        0x00,
+    ]);
+    let fmt = format!("{:x}", uuid);
+    assert_eq!(fmt, "123e4567-e89b-12d3-a456-426614174000");
+    assert_eq!(
+        format!("{:#x}", uuid),
+        "0x123e4567-e89b-12d3-a456-426614174000"
+    );
+    assert_eq!(fmt.parse::<Uuid>(), Ok(uuid));
+
+    let fingerprint = Fingerprint(vec![0xde, 0xad, 0xbe, 0xef, 0x01]);
+    let fmt = format!("{:x}", fingerprint);
+    assert_eq!(fmt, "dead beef 01");
+    assert_eq!(fmt.parse::<Fingerprint>(), Ok(fingerprint));
+    assert_eq!(
+        "0xdead be".parse::<Fingerprint>().unwrap().0,
+        vec![0xde, 0xad, 0xbe]
+    );
+    assert_eq!(
+        "dead b".parse::<Fingerprint>(),
+        Err(ParseFingerprintError::InvalidLength)
+    );
+
+    let serial = Serial {
+        bytes: [0x0b, 0xad, 0xf0, 0x0d],
+        revision: 0,
+    };
+    let fmt = format!("{:x}", serial);
+    assert_eq!(fmt, "0bad-f00d");
+    assert_eq!(fmt.parse::<Serial>(), Ok(serial));
+}
+
+fn test_fmt_traits() {
+    let reg = Register(0xbeef);
+    assert_eq!(reg.to_string(), "BEEF");
+    assert_eq!(
+        format!("{:#X} {:o} {:#b}", reg, reg, reg),
+        "0xBEEF 137357 0b1011111011101111"
+    );
+
+    let m = Meters(1234.5);
+    assert_eq!(m.to_string(), "1.2345E3");
+    assert_eq!(format!("{:e} {:.1e}", m, m), "1.2345e3 1.2e3");
+
+    let bits = Bits([0x0a, 0xf1]);
+    assert_eq!(bits.to_string(), "0000101011110001");
+    assert_eq!(format!("{:X} {:#X}", bits, bits), "0AF1 0x0AF1");
+    assert_eq!(format!("{:o} {:#o}", bits, bits), "012361 0o012361");
+    assert_eq!(
+        format!("{:.5b} {:.4o} {:.3X}", bits, bits, bits),
+        "00001 0123 0AF"
+    );
+    assert_eq!(format!("{:>20b}|", bits), "    0000101011110001|");
+}
+
+fn test_hex_elements() {
+    #[cfg(not(MSRV))]
+    {
+        let midstate = Midstate([1, 0x100, 0x6a09e667, 0xffffffff]);
+        let fmt = format!("{:x}", midstate);
+        assert_eq!(fmt, "00000001 00000100 6a09e667 ffffffff");
+        assert_eq!(
+            format!("{:X}", midstate),
+            "00000001 00000100 6A09E667 FFFFFFFF"
+        );
+        assert_eq!(format!("{:.16x}", midstate), "00000001 00000100");
+        assert_eq!(format!("{:.11x}", midstate), "00000001 000");
+        assert_eq!(fmt.parse::<Midstate>(), Ok(midstate));
+
+        let words = Words([1, 0x1234, 0xff00]);
+        let fmt = format!("{:#x}", words);
+        assert_eq!(fmt, "0x0100341200ff");
+        assert_eq!(fmt.parse::<Words>(), Ok(words));
+    }
+}
+
+fn test_iter_matches_per_byte() {
+    fn per_byte(bytes: &[u8], group: usize, format: fn(&u8) -> String) -> String {
+        let mut res = String::new();
+        for (i, byte) in bytes.iter().enumerate() {
+            if i != 0 && i % group == 0 {
+                res.push('\u{2219}');
+            }
+            res.push_str(&format(byte));
+        }
+        res
+    }
+
+    for len in [0, 1, 31, 32, 33, 100, 1000].iter() {
+        let bytes: Vec<u8> = (0..*len).map(|i| (i * 37 + 11) as u8).collect();
+        let heap = Heap(bytes.clone().into_boxed_slice());
+        let lower = per_byte(&bytes, bytes.len() + 1, |b| format!("{:02x}", b));
+        assert_eq!(format!("{:x}", heap), lower);
+        assert_eq!(format!("{:#x}", heap), format!("0x{}", lower));
+
+        let dotted = Dotted(bytes.clone());
+        let upper = per_byte(&bytes, 3, |b| format!("{:02X}", b));
+        assert_eq!(format!("{:X}", dotted), upper);
+        let lower = per_byte(&bytes, 3, |b| format!("{:02x}", b));
+        assert_eq!(format!("{:x}", dotted), lower);
+        assert_eq!(
+            format!("{:o}", dotted),
+            per_byte(&bytes, 3, |b| format!("{:03o}", b))
+        );
+        assert_eq!(
+            format!("{:b}", dotted),
+            per_byte(&bytes, 3, |b| format!("{:08b}", b))
+        );
+
+        let width = lower.chars().count() + 4;
+        assert_eq!(
+            format!("{:^1$x}", dotted, width),
+            format!("{:^1$}", lower, width)
+        );
+    }
+}
+
 fn test_as_ref() {
     let a = Me::default();
     a.as_ref();
@@ -178,6 +1520,13 @@ fn test_readme() {
     #[derive(Debug, Default, LowerHex)]
     struct Flag(i32);
 
+    #[derive(ArrayDebug, ArrayDefault, ArrayClone, ArrayHash, LowerHexIter)]
+    struct Signature([u8; 64]);
+
+    #[derive(Debug, LowerHexIter, FromHexIter)]
+    #[hex(groups = [4, 2, 2, 2, 6], separator = "-")]
+    struct Uuid([u8; 16]);
+
     #[derive(Debug, Index, LowerHexIter, Default)]
     struct Hi {
         #[wrap]
@@ -185,6 +1534,10 @@ fn test_readme() {
         b: Flag,
     }
 
+    #[derive(Redacted)]
+    #[redacted(fingerprint = "len")]
+    struct ApiKey(Vec<u8>);
+
     #[derive(Debug, Display, From, Error)]
     #[display_from(Debug)]
     struct Printer<T: std::fmt::Debug>(T);
@@ -216,16 +1569,58 @@ fn test_readme() {
         #[derive_from(std::io::Error, std::fmt::Error)]
         Errors,
     }
+
+    #[cfg(not(MSRV))]
+    #[derive(Debug, Error)]
+    enum ConfigError {
+        #[error("can't read {path}: {source}")]
+        Read {
+            path: String,
+            #[source]
+            source: std::io::Error,
+        },
+        #[error("invalid port {0}")]
+        Port(u16),
+        #[error(transparent)]
+        #[derive_from]
+        Fmt(std::fmt::Error),
+    }
 }
 
 fn main() {
     test_readme();
     test_lowerhex();
+    test_lowerhex_iter_flags();
+    test_hex_separators();
+    test_fmt_traits();
+    test_hex_elements();
+    test_iter_matches_per_byte();
     test_as_ref();
     test_display();
     test_from();
     test_index_heap();
     test_error();
+    test_error_source();
+    test_error_enum();
+    test_error_transparent();
+    test_error_chain();
+    test_error_codes();
+    test_error_io_kind();
+    test_error_trait();
+    test_arithmetic();
+    test_assign_ops();
+    test_scalar_ops();
+    test_integer_ops();
+    test_flags();
+    test_sum_product();
+    test_byte_order();
+    test_decimal();
+    test_compare_wrapped();
+    test_compare_inner();
+    test_array_traits();
+    test_debug_modes();
+    test_redacted();
+    test_iter_wide_elements();
     test_from_enum();
 }
 
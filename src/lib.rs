@@ -7,9 +7,12 @@ extern crate quote;
 extern crate syn;
 
 use syn::export::{Span, ToTokens, TokenStream, TokenStream2};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::{
-    Attribute, Data, DataEnum, DeriveInput, Error, Field, Fields, Ident, Index, Lit, Member, Meta,
-    NestedMeta, Path, Result, Type, TypeSlice,
+    Attribute, Data, DataEnum, DeriveInput, Error, Expr, Field, Fields, GenericArgument,
+    GenericParam, Generics, Ident, Index, Lifetime, LifetimeDef, Lit, LitByteStr, LitInt, LitStr,
+    Member, Meta, NestedMeta, Path, PathArguments, Result, Type, TypeSlice, Variant,
 };
 
 struct Details<'a> {
@@ -54,20 +57,92 @@ pub fn derive_index(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(LowerHex, attributes(wrap))]
 pub fn derive_lowerhex(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
-    lowerhex_inner(derive_input)
+    fmt_inner(derive_input, "LowerHex")
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
 
-#[proc_macro_derive(LowerHexIter, attributes(wrap))]
+#[proc_macro_derive(LowerHexIter, attributes(wrap, hex))]
 pub fn derive_lowerhex_iter(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
-    lowerhexiter_inner(derive_input)
+    fmt_iter_inner(derive_input, &LOWER_HEX)
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
 
-#[proc_macro_derive(Display, attributes(wrap, display_from))]
+#[proc_macro_derive(UpperHex, attributes(wrap))]
+pub fn derive_upperhex(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_inner(derive_input, "UpperHex")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(UpperHexIter, attributes(wrap, hex))]
+pub fn derive_upperhex_iter(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_iter_inner(derive_input, &UPPER_HEX)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Octal, attributes(wrap))]
+pub fn derive_octal(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_inner(derive_input, "Octal")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(OctalIter, attributes(wrap, hex))]
+pub fn derive_octal_iter(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_iter_inner(derive_input, &OCTAL)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Binary, attributes(wrap))]
+pub fn derive_binary(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_inner(derive_input, "Binary")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BinaryIter, attributes(wrap, hex))]
+pub fn derive_binary_iter(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_iter_inner(derive_input, &BINARY)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(LowerExp, attributes(wrap))]
+pub fn derive_lowerexp(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_inner(derive_input, "LowerExp")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(UpperExp, attributes(wrap))]
+pub fn derive_upperexp(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fmt_inner(derive_input, "UpperExp")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(FromHexIter, attributes(wrap, hex))]
+pub fn derive_from_hex_iter(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fromhexiter_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Display, attributes(wrap, display_from, error))]
 pub fn derive_display(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     display_inner(derive_input)
@@ -83,7 +158,10 @@ pub fn derive_from(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(Error)]
+#[proc_macro_derive(
+    Error,
+    attributes(wrap, source, error, derive_from, error_code, io_kind)
+)]
 pub fn derive_error(input: TokenStream) -> TokenStream {
     let derive_input = parse_macro_input!(input as DeriveInput);
     error_inner(derive_input)
@@ -91,240 +169,3536 @@ pub fn derive_error(input: TokenStream) -> TokenStream {
         .into()
 }
 
-fn error_inner(input: DeriveInput) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let type_name = &input.ident;
-    let std = std();
+#[proc_macro_derive(Add, attributes(wrap))]
+pub fn derive_add(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "Add", "add")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-    Ok(quote! {
-        #[allow(unused_qualifications)]
-        impl #impl_generics #std::error::Error for #type_name #ty_generics #where_clause {
-            #[inline]
-            fn description(&self) -> &str {
-                "description() is deprecated; use Display"
-            }
-        }
-    })
+#[proc_macro_derive(Sub, attributes(wrap))]
+pub fn derive_sub(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "Sub", "sub")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
-fn from_inner(input: DeriveInput) -> Result<TokenStream2> {
-    match input.data {
-        Data::Struct(_) => from_inner_struct(&input),
-        Data::Enum(ref data) => from_inner_enum(&input, &data),
-        Data::Union(_) => Err(Error::new_spanned(
-            &input,
-            "Deriving From is not supported in unions",
-        )),
-    }
+#[proc_macro_derive(Mul, attributes(wrap))]
+pub fn derive_mul(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "Mul", "mul")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
 
-fn from_inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let std = std();
-    let mut res = TokenStream2::default();
-    let enum_name = &input.ident;
+#[proc_macro_derive(Div, attributes(wrap))]
+pub fn derive_div(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "Div", "div")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-    for variant in &data.variants {
-        let variant_name = &variant.ident;
-        for attr in &variant.attrs {
-            if attr.path.is_ident("derive_from") {
-                if variant.fields.iter().len() > 1 {
-                    return Err(Error::new_spanned(
-                        &variant,
-                        "Deriving From for an enum variant with multiple fields isn't supported",
-                    ));
-                }
+#[proc_macro_derive(Rem, attributes(wrap))]
+pub fn derive_rem(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "Rem", "rem")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-                let mut froms = Vec::new();
-                if attr.tts.is_empty() {
-                    let field = variant.fields.iter().next().ok_or_else(||Error::new_spanned(&variant,
-                                                                                             "Deriving From for an enum variant without fields require explicit From type. Try: `#[derive_from(FromType)]`"))?;
-                    match field.ident {
-                        Some(ref field_name) => {
-                            froms.push((field.ty.clone(), quote! {{#field_name: inner}}))
-                        }
-                        None => froms.push((field.ty.clone(), quote! {(inner)})),
-                    };
-                } else {
-                    let types = extract_types_from_potential_tupled_attribute(&attr)?;
-                    for ty in types {
-                        froms.push((ty, quote! {}))
+#[proc_macro_derive(Neg, attributes(wrap))]
+pub fn derive_neg(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    unary_op_inner(derive_input, "Neg", "neg")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BitAnd, attributes(wrap))]
+pub fn derive_bitand(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "BitAnd", "bitand")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BitOr, attributes(wrap))]
+pub fn derive_bitor(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "BitOr", "bitor")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BitXor, attributes(wrap))]
+pub fn derive_bitxor(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    binary_op_inner(derive_input, "BitXor", "bitxor")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Not, attributes(wrap))]
+pub fn derive_not(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    unary_op_inner(derive_input, "Not", "not")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Shl, attributes(wrap))]
+pub fn derive_shl(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    shift_inner(derive_input, "Shl", "shl")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Shr, attributes(wrap))]
+pub fn derive_shr(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    shift_inner(derive_input, "Shr", "shr")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Flags, attributes(wrap, flags))]
+pub fn derive_flags(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    flags_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Sum, attributes(wrap, fold))]
+pub fn derive_sum(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fold_inner(derive_input, "Sum", "sum")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Product, attributes(wrap, fold))]
+pub fn derive_product(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    fold_inner(derive_input, "Product", "product")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ByteOrder, attributes(wrap, byte_order))]
+pub fn derive_byte_order(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    byte_order_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Decimal, attributes(wrap, decimal))]
+pub fn derive_decimal(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    decimal_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(WrapperPartialEq, attributes(wrap))]
+pub fn derive_wrapper_partial_eq(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    compare_inner(derive_input, "WrapperPartialEq")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(WrapperEq, attributes(wrap))]
+pub fn derive_wrapper_eq(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    compare_inner(derive_input, "WrapperEq")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(WrapperPartialOrd, attributes(wrap))]
+pub fn derive_wrapper_partial_ord(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    compare_inner(derive_input, "WrapperPartialOrd")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(WrapperOrd, attributes(wrap))]
+pub fn derive_wrapper_ord(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    compare_inner(derive_input, "WrapperOrd")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(WrapperHash, attributes(wrap))]
+pub fn derive_wrapper_hash(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    compare_inner(derive_input, "WrapperHash")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(InnerPartialEq, attributes(wrap))]
+pub fn derive_inner_partial_eq(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    cross_compare_inner(derive_input, "InnerPartialEq")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(InnerPartialOrd, attributes(wrap))]
+pub fn derive_inner_partial_ord(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    cross_compare_inner(derive_input, "InnerPartialOrd")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ArrayDefault, attributes(wrap))]
+pub fn derive_array_default(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    array_trait_inner(derive_input, "ArrayDefault")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ArrayDebug, attributes(wrap))]
+pub fn derive_array_debug(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    array_trait_inner(derive_input, "ArrayDebug")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ArrayHash, attributes(wrap))]
+pub fn derive_array_hash(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    array_trait_inner(derive_input, "ArrayHash")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ArrayClone, attributes(wrap))]
+pub fn derive_array_clone(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    array_trait_inner(derive_input, "ArrayClone")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(WrapperDebug, attributes(wrap, debug, hex))]
+pub fn derive_wrapper_debug(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    debug_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Redacted, attributes(wrap, redacted))]
+pub fn derive_redacted(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    redacted_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(AddAssign, attributes(wrap))]
+pub fn derive_add_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "AddAssign", "add_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(SubAssign, attributes(wrap))]
+pub fn derive_sub_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "SubAssign", "sub_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(MulAssign, attributes(wrap))]
+pub fn derive_mul_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "MulAssign", "mul_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(DivAssign, attributes(wrap))]
+pub fn derive_div_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "DivAssign", "div_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(RemAssign, attributes(wrap))]
+pub fn derive_rem_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "RemAssign", "rem_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BitAndAssign, attributes(wrap))]
+pub fn derive_bitand_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "BitAndAssign", "bitand_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BitOrAssign, attributes(wrap))]
+pub fn derive_bitor_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "BitOrAssign", "bitor_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(BitXorAssign, attributes(wrap))]
+pub fn derive_bitxor_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "BitXorAssign", "bitxor_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ShlAssign, attributes(wrap))]
+pub fn derive_shl_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "ShlAssign", "shl_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(ShrAssign, attributes(wrap))]
+pub fn derive_shr_assign(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    assign_op_inner(derive_input, "ShrAssign", "shr_assign")
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Ops, attributes(wrap, ops))]
+pub fn derive_ops(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    ops_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(IntegerOps, attributes(wrap, integer_ops))]
+pub fn derive_integer_ops(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    integer_ops_inner(derive_input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn error_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let error = match input.data {
+        Data::Enum(ref data) => error_inner_enum(&input, data)?,
+        _ => error_inner_struct(&input)?,
+    };
+    let io_error = generate_io_error(&input)?;
+
+    Ok(quote! {
+        #error
+        #io_error
+    })
+}
+
+fn get_io_kind(attrs: &[Attribute]) -> Result<Option<Ident>> {
+    let mut res = None;
+    for attr in attrs {
+        if attr.path.is_ident("io_kind") {
+            if res.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "derive_wrapper: supports only a single io_kind attribute",
+                ));
+            }
+            let kind = syn::parse2::<ParenthesizedIdent>(attr.tts.clone())
+                .map_err(|e| Error::new(e.span(), format!("{}. Try: `#[io_kind(NotFound)]`", e)))?;
+            res = Some(kind.0);
+        }
+    }
+    Ok(res)
+}
+
+// Generates `From<Self> for io::Error` when io_kind is used. The error is kept as the custom payload,
+// the type level io_kind is the kind for variants without one and defaults to `Other`.
+fn generate_io_error(input: &DeriveInput) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let type_name = &input.ident;
+    let std = std();
+    let default_kind = get_io_kind(&input.attrs)?;
+    let mut arms = Vec::new();
+    let mut variants = 0;
+    if let Data::Enum(ref data) = input.data {
+        variants = data.variants.len();
+        for variant in &data.variants {
+            if let Some(kind) = get_io_kind(&variant.attrs)? {
+                let variant_name = &variant.ident;
+                arms.push(quote!(#type_name::#variant_name { .. } => #std::io::ErrorKind::#kind));
+            }
+        }
+    }
+    if default_kind.is_none() && arms.is_empty() {
+        return Ok(quote!());
+    }
+    if !cfg!(feature = "std") {
+        return Err(Error::new_spanned(
+            input,
+            "derive_wrapper: converting errors into io::Error requires the std feature",
+        ));
+    }
+    if arms.len() < variants || variants == 0 {
+        let kind = default_kind.unwrap_or_else(|| Ident::new("Other", Span::call_site()));
+        arms.push(quote!(_ => #std::io::ErrorKind::#kind));
+    }
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::convert::From<#type_name #ty_generics> for #std::io::Error #where_clause {
+            fn from(error: #type_name #ty_generics) -> Self {
+                let kind = match error {
+                    #(#arms,)*
+                };
+                #std::io::Error::new(kind, error)
+            }
+        }
+    })
+}
+
+fn error_inner_struct(input: &DeriveInput) -> Result<TokenStream2> {
+    let std = std();
+    let options = ErrorAttr::from_attrs(&input.attrs)?.unwrap_or_default();
+    let error_trait = options.error_trait();
+    if options.message.is_some() {
+        return Err(Error::new_spanned(
+            input,
+            "derive_wrapper: error messages are supported only on enum variants. Try: `#[error(transparent)]`",
+        ));
+    }
+    if options.transparent {
+        return error_transparent_struct(input, &error_trait, &options);
+    }
+
+    let source = match get_source_field(input)? {
+        Some(field) => {
+            let Details { field_name, .. } = Details::from_input(&input.ident, field);
+            let source = source_expr(&error_trait, quote!(&self.#field_name), &field.ty);
+            quote! {
+                #[inline]
+                fn source(&self) -> #std::option::Option<&(dyn (#error_trait) + 'static)> {
+                    #source
+                }
+            }
+        }
+        None => quote!(),
+    };
+
+    Ok(generate_error_impl(input, &options, source))
+}
+
+// A transparent error forwards Display, source() and optionally Debug to the wrapped error.
+fn error_transparent_struct(
+    input: &DeriveInput,
+    error_trait: &TokenStream2,
+    options: &ErrorAttr,
+) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field = get_field(input, "Error")?;
+    let Details {
+        struct_name,
+        field_name,
+        std,
+        ..
+    } = Details::from_input(&input.ident, field);
+
+    let source = quote! {
+        #[inline]
+        fn source(&self) -> #std::option::Option<&(dyn (#error_trait) + 'static)> {
+            #error_trait::source(&self.#field_name)
+        }
+    };
+    let error_impl = generate_error_impl(input, options, source);
+    let display = forward_display(&quote!(&self.#field_name), error_trait, options);
+    let debug_impl = if options.debug {
+        quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::fmt::Debug for #struct_name #ty_generics #where_clause {
+                #[inline]
+                fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                    #std::fmt::Debug::fmt(&self.#field_name, f)
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        #error_impl
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Display for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                #display
+            }
+        }
+
+        #debug_impl
+    })
+}
+
+fn error_inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let enum_name = &input.ident;
+    let std = std();
+    let options = ErrorAttr::from_attrs(&input.attrs)?.unwrap_or_default();
+    let error_trait = options.error_trait();
+    if options.message.is_some() || options.transparent || options.debug {
+        return Err(Error::new_spanned(
+            input,
+            "derive_wrapper: error messages and transparent errors are set on each enum variant",
+        ));
+    }
+
+    let mut messages = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        messages.push(ErrorAttr::from_attrs(&variant.attrs)?);
+    }
+    // Without messages the enum keeps implementing only Error, with them Display and From are derived as well.
+    let with_messages = messages.iter().any(Option::is_some);
+
+    let mut display_arms = Vec::with_capacity(data.variants.len());
+    let mut source_arms = Vec::with_capacity(data.variants.len());
+    let mut has_source = false;
+    for (variant, message) in data.variants.iter().zip(messages) {
+        let variant_name = &variant.ident;
+        let fields: Vec<&Field> = variant.fields.iter().collect();
+        let message = match message {
+            Some(message) => message,
+            None if with_messages => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "Deriving Error with messages requires an error attribute on every variant. Try: `#[error(\"message {field}\")]`",
+                ))
+            }
+            None => ErrorAttr::default(),
+        };
+        if message.debug {
+            return Err(Error::new_spanned(
+                variant,
+                "derive_wrapper: forwarding Debug is supported only on transparent structs",
+            ));
+        }
+
+        let source = if message.transparent {
+            if fields.len() != 1 {
+                return Err(Error::new_spanned(
+                    variant,
+                    "derive_wrapper: a transparent error variant must have exactly one field",
+                ));
+            }
+            let binding = field_binding(fields[0], 0);
+            let pattern = variant_pattern(enum_name, variant, &[0]);
+            // The chain is written once after the match, so only the inner message goes here.
+            let display = match options.chain {
+                Some(_) => quote! {
+                    if f.alternate() {
+                        write!(f, "{}", #binding)
+                    } else {
+                        #std::fmt::Display::fmt(#binding, f)
+                    }
+                },
+                None => quote!(#std::fmt::Display::fmt(#binding, f)),
+            };
+            display_arms.push(quote!(#pattern => { #display }));
+            Some(quote!(#pattern => #error_trait::source(#binding)))
+        } else {
+            if let Some(ref message) = message.message {
+                let (format, args) = parse_error_message(message, &fields)?;
+                let bindings = args.iter().map(|&i| {
+                    let binding = field_binding(fields[i], i);
+                    quote!(#binding = #binding)
+                });
+                let pattern = variant_pattern(enum_name, variant, &args);
+                display_arms.push(quote!(#pattern => write!(f, #format, #(#bindings),*)));
+            }
+            let marked = fields
+                .iter()
+                .position(|field| field.attrs.iter().any(|attr| attr.path.is_ident("source")));
+            let derive_from = with_messages
+                && fields.len() == 1
+                && variant
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path.is_ident("derive_from") && attr.tts.is_empty());
+            match marked.or(if derive_from { Some(0) } else { None }) {
+                Some(i) => {
+                    let binding = field_binding(fields[i], i);
+                    let pattern = variant_pattern(enum_name, variant, &[i]);
+                    let source =
+                        source_expr(&error_trait, binding.into_token_stream(), &fields[i].ty);
+                    Some(quote!(#pattern => #source))
+                }
+                None => None,
+            }
+        };
+        match source {
+            Some(arm) => {
+                has_source = true;
+                source_arms.push(arm);
+            }
+            None => source_arms
+                .push(quote!(#enum_name::#variant_name { .. } => #std::option::Option::None)),
+        }
+    }
+
+    let source = if has_source {
+        quote! {
+            fn source(&self) -> #std::option::Option<&(dyn (#error_trait) + 'static)> {
+                match *self {
+                    #(#source_arms,)*
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+    let error_impl = generate_error_impl(input, &options, source);
+    let codes = generate_error_codes(input, data)?;
+    if !with_messages {
+        return Ok(quote! {
+            #error_impl
+            #codes
+        });
+    }
+    let from_impls = from_inner_enum(input, data)?;
+    let display = match options.chain {
+        Some(ref separator) => {
+            let chain = generate_chain(&error_trait, separator);
+            quote! {
+                match *self {
+                    #(#display_arms,)*
+                }?;
+                #chain
+            }
+        }
+        None => quote! {
+            match *self {
+                #(#display_arms,)*
+            }
+        },
+    };
+
+    Ok(quote! {
+        #error_impl
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Display for #enum_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                #display
+            }
+        }
+
+        #from_impls
+        #codes
+    })
+}
+
+struct ParenthesizedIdent(Ident);
+
+impl Parse for ParenthesizedIdent {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        Ok(ParenthesizedIdent(content.parse()?))
+    }
+}
+
+enum ErrorCode {
+    Code(i32),
+    Delegate,
+}
+
+// Accepts `= 1`, `(-1)` for negative codes, which aren't literals in attributes, and `(delegate)`.
+impl Parse for ErrorCode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let input = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            input
+        } else {
+            parenthesized!(content in input);
+            if content.peek(Ident) {
+                let name: Ident = content.parse()?;
+                if name == "delegate" && content.is_empty() {
+                    return Ok(ErrorCode::Delegate);
+                }
+                return Err(Error::new(name.span(), "derive_wrapper: unknown error code option. Try: `#[error_code = 1]` or `#[error_code(delegate)]`"));
+            }
+            &content
+        };
+        let negative = input.peek(Token![-]);
+        if negative {
+            input.parse::<Token![-]>()?;
+        }
+        let lit: LitInt = input.parse()?;
+        // Checked before converting, `-i32::MIN` doesn't fit in an i32 itself.
+        if lit.value() > i32::MAX as u64 + negative as u64 {
+            return Err(Error::new(
+                lit.span(),
+                "derive_wrapper: error codes must fit in an i32",
+            ));
+        }
+        let value = if negative {
+            -(lit.value() as i64)
+        } else {
+            lit.value() as i64
+        };
+        Ok(ErrorCode::Code(value as i32))
+    }
+}
+
+// Generates `code()` and `CODES` when the variants are marked with error codes, rejecting duplicate codes.
+fn generate_error_codes(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let enum_name = &input.ident;
+    let mut codes: Vec<(i32, String)> = Vec::new();
+    let mut arms = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let mut code = None;
+        for attr in &variant.attrs {
+            if attr.path.is_ident("error_code") {
+                if code.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: supports only a single error_code attribute",
+                    ));
+                }
+                code = Some(syn::parse2::<ErrorCode>(attr.tts.clone())?);
+            }
+        }
+        match code {
+            Some(ErrorCode::Code(code)) => {
+                if let Some((_, other)) = codes.iter().find(|&&(c, _)| c == code) {
+                    return Err(Error::new_spanned(
+                        variant,
+                        format!(
+                            "derive_wrapper: the error code {} is already used by {}",
+                            code, other
+                        ),
+                    ));
+                }
+                codes.push((code, variant_name.to_string()));
+                arms.push(quote!(#enum_name::#variant_name { .. } => #code));
+            }
+            Some(ErrorCode::Delegate) => {
+                let field = match variant.fields.iter().next() {
+                    Some(field) if variant.fields.iter().len() == 1 => field,
+                    _ => {
+                        return Err(Error::new_spanned(
+                            variant,
+                            "derive_wrapper: delegating the error code requires a variant with a single field",
+                        ))
+                    }
+                };
+                let binding = field_binding(field, 0);
+                let pattern = variant_pattern(enum_name, variant, &[0]);
+                arms.push(quote!(#pattern => #binding.code()));
+            }
+            None => (),
+        }
+    }
+    if arms.is_empty() {
+        return Ok(quote!());
+    }
+    if arms.len() != data.variants.len() {
+        return Err(Error::new_spanned(
+            input,
+            "Deriving Error with error codes requires an error_code attribute on every variant. Try: `#[error_code = 1]`",
+        ));
+    }
+    let codes = codes.iter().map(|&(code, ref name)| quote!((#code, #name)));
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            /// Every error code with the name of its variant, without the delegated codes.
+            pub const CODES: &'static [(i32, &'static str)] = &[#(#codes),*];
+
+            /// Returns the numeric code of the error.
+            pub fn code(&self) -> i32 {
+                match *self {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
+
+// Displays the wrapped error of a transparent error, without its chain when the wrapper prints the chain itself.
+fn forward_display(
+    reference: &TokenStream2,
+    error_trait: &TokenStream2,
+    options: &ErrorAttr,
+) -> TokenStream2 {
+    let std = std();
+    match options.chain {
+        Some(ref separator) => {
+            let chain = generate_chain(error_trait, separator);
+            quote! {
+                if f.alternate() {
+                    write!(f, "{}", #reference)?;
+                } else {
+                    #std::fmt::Display::fmt(#reference, f)?;
+                }
+                #chain
+            }
+        }
+        None => quote!(#std::fmt::Display::fmt(#reference, f)),
+    }
+}
+
+fn generate_error_impl(
+    input: &DeriveInput,
+    options: &ErrorAttr,
+    source: TokenStream2,
+) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let type_name = &input.ident;
+    let error_trait = options.error_trait();
+    // A custom error trait isn't required to have the deprecated description().
+    let description = if options.error_trait.is_none() {
+        quote! {
+            #[inline]
+            fn description(&self) -> &str {
+                "description() is deprecated; use Display"
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #error_trait for #type_name #ty_generics #where_clause {
+            #description
+            #source
+        }
+    }
+}
+
+// The name a variant field is bound to in generated match arms, `_name` for named fields and `_0` for unnamed.
+fn field_binding(field: &Field, index: usize) -> Ident {
+    match field.ident {
+        Some(ref ident) => Ident::new(&format!("_{}", ident), ident.span()),
+        None => Ident::new(&format!("_{}", index), Span::call_site()),
+    }
+}
+
+// A pattern matching `variant` which binds by reference only the fields at `used`.
+fn variant_pattern(enum_name: &Ident, variant: &Variant, used: &[usize]) -> TokenStream2 {
+    let variant_name = &variant.ident;
+    match variant.fields {
+        Fields::Named(ref fields) => {
+            let bindings = fields
+                .named
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| used.contains(&i))
+                .map(|(i, field)| {
+                    let name = &field.ident;
+                    let binding = field_binding(field, i);
+                    quote!(#name: ref #binding)
+                });
+            quote!(#enum_name::#variant_name { #(#bindings,)* .. })
+        }
+        Fields::Unnamed(ref fields) => {
+            let bindings = fields.unnamed.iter().enumerate().map(|(i, field)| {
+                if used.contains(&i) {
+                    let binding = field_binding(field, i);
+                    quote!(ref #binding)
+                } else {
+                    quote!(_)
+                }
+            });
+            quote!(#enum_name::#variant_name(#(#bindings),*))
+        }
+        Fields::Unit => quote!(#enum_name::#variant_name),
+    }
+}
+
+// Rewrites the `{field}` and `{0}` arguments of an error message into the field bindings,
+// returning the new format string and the indices of the fields it uses.
+fn parse_error_message(message: &LitStr, fields: &[&Field]) -> Result<(LitStr, Vec<usize>)> {
+    let value = message.value();
+    let mut format = String::with_capacity(value.len());
+    let mut used = Vec::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        format.push(c);
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            format.push('{');
+            chars.next();
+            continue;
+        }
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '}' || c == ':' {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+        let index = fields
+            .iter()
+            .enumerate()
+            .position(|(i, field)| match field.ident {
+                Some(ref ident) => ident == &name,
+                None => i.to_string() == name,
+            });
+        let index = match index {
+            Some(index) => index,
+            None => {
+                return Err(Error::new(
+                    message.span(),
+                    format!("derive_wrapper: the error message argument `{{{}}}` isn't a field of the variant", name),
+                ))
+            }
+        };
+        format.push_str(&field_binding(fields[index], index).to_string());
+        if !used.contains(&index) {
+            used.push(index);
+        }
+    }
+    Ok((LitStr::new(&format, message.span()), used))
+}
+
+#[derive(Default)]
+struct ErrorAttr {
+    message: Option<LitStr>,
+    transparent: bool,
+    debug: bool,
+    chain: Option<LitStr>,
+    error_trait: Option<Path>,
+}
+
+impl Parse for ErrorAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let mut res = ErrorAttr::default();
+        if content.peek(LitStr) {
+            res.message = Some(content.parse()?);
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        while !content.is_empty() {
+            if content.peek(Token![trait]) {
+                content.parse::<Token![trait]>()?;
+                content.parse::<Token![=]>()?;
+                res.error_trait = Some(content.parse()?);
+                if !content.is_empty() {
+                    content.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+            let name: Ident = content.parse()?;
+            if name == "transparent" {
+                res.transparent = true;
+            } else if name == "debug" {
+                res.debug = true;
+            } else if name == "chain" {
+                res.chain = Some(if content.peek(Token![=]) {
+                    content.parse::<Token![=]>()?;
+                    content.parse()?
+                } else {
+                    LitStr::new(": ", name.span())
+                });
+            } else {
+                return Err(Error::new(
+                    name.span(),
+                    format!(
+                        "derive_wrapper: unknown error option `{}`. Supported options: transparent, debug, chain, trait",
+                        name
+                    ),
+                ));
+            }
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        if res.debug && !res.transparent {
+            return Err(Error::new(
+                Span::call_site(),
+                "derive_wrapper: forwarding Debug requires a transparent error. Try: `#[error(transparent, debug)]`",
+            ));
+        }
+        if res.transparent && res.message.is_some() {
+            return Err(Error::new(
+                Span::call_site(),
+                "derive_wrapper: a transparent error can't have a message",
+            ));
+        }
+        Ok(res)
+    }
+}
+
+impl ErrorAttr {
+    // The trait to implement, std's (or core's without the std feature) unless another is set with `trait = path`.
+    pub fn error_trait(&self) -> TokenStream2 {
+        match self.error_trait {
+            Some(ref path) => path.into_token_stream(),
+            None => {
+                let std = std();
+                quote!(#std::error::Error)
+            }
+        }
+    }
+
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Option<Self>> {
+        let mut res: Option<ErrorAttr> = None;
+        for attr in attrs {
+            if attr.path.is_ident("error") {
+                if res.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: supports only a single error attribute",
+                    ));
+                }
+                res = Some(syn::parse2(attr.tts.clone())?);
+            }
+        }
+        Ok(res)
+    }
+}
+
+// The source is the field marked with `#[source]`, otherwise the wrapped field if a wrap attribute is used.
+fn get_source_field(input: &DeriveInput) -> Result<Option<&Field>> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => return Ok(None),
+    };
+    let mut sources: Vec<_> = fields
+        .iter()
+        .filter(|field| field.attrs.iter().any(|attr| attr.path.is_ident("source")))
+        .collect();
+    match sources.len() {
+        0 => (),
+        1 => return Ok(sources.pop()),
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "Deriving Error supports only a single source attribute",
+            ))
+        }
+    }
+
+    let wrapped = !parse_outer_attributes(&input.attrs, fields)?.is_empty()
+        || !parse_field_attributes(fields)?.is_empty();
+    if wrapped {
+        get_field(input, "Error").map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+// Converts `reference`, a reference to a source of type `ty`, into an `Option<&dyn Error>`.
+// `Option<E>` and `Box<E>` sources are unwrapped so boxed trait objects can be used as well.
+// The trait is parenthesized so `dyn ::std::...` isn't parsed as a path on the 2015 edition.
+fn source_expr(error_trait: &TokenStream2, reference: TokenStream2, ty: &Type) -> TokenStream2 {
+    let std = std();
+    match type_argument(ty, "Option") {
+        Some(inner) => {
+            let deref = if type_argument(inner, "Box").is_some() {
+                quote!(&**)
+            } else {
+                quote!()
+            };
+            quote! {
+                (#reference).as_ref().map(|source| #deref source as &(dyn (#error_trait) + 'static))
+            }
+        }
+        None => {
+            let deref = if type_argument(ty, "Box").is_some() {
+                quote!(&**)
+            } else {
+                quote!()
+            };
+            quote!(#std::option::Option::Some(#deref #reference as &(dyn (#error_trait) + 'static)))
+        }
+    }
+}
+
+fn from_inner(input: DeriveInput) -> Result<TokenStream2> {
+    match input.data {
+        Data::Struct(_) => from_inner_struct(&input),
+        Data::Enum(ref data) => from_inner_enum(&input, data),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input,
+            "Deriving From is not supported in unions",
+        )),
+    }
+}
+
+fn from_inner_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let std = std();
+    let mut res = TokenStream2::default();
+    let enum_name = &input.ident;
+
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        for attr in &variant.attrs {
+            if attr.path.is_ident("derive_from") {
+                if variant.fields.iter().len() > 1 {
+                    return Err(Error::new_spanned(
+                        variant,
+                        "Deriving From for an enum variant with multiple fields isn't supported",
+                    ));
+                }
+
+                let mut froms = Vec::new();
+                if attr.tts.is_empty() {
+                    let field = variant.fields.iter().next().ok_or_else(||Error::new_spanned(variant,
+                                                                                             "Deriving From for an enum variant without fields require explicit From type. Try: `#[derive_from(FromType)]`"))?;
+                    match field.ident {
+                        Some(ref field_name) => {
+                            froms.push((field.ty.clone(), quote! {{#field_name: inner}}))
+                        }
+                        None => froms.push((field.ty.clone(), quote! {(inner)})),
+                    };
+                } else {
+                    let types = extract_types_from_potential_tupled_attribute(attr)?;
+                    for ty in types {
+                        froms.push((ty, quote! {}))
+                    }
+                }
+                for from in froms {
+                    let field_type = from.0;
+                    let postfix = from.1;
+                    res = quote! {
+                        #res
+                        #[allow(unused_qualifications)]
+                        impl #impl_generics #std::convert::From<#field_type> for #enum_name #ty_generics #where_clause {
+                            #[inline]
+                            fn from(inner: #field_type) -> Self {
+                                #enum_name::#variant_name#postfix
+                            }
+                        }
+                    };
+                }
+            }
+        }
+    }
+    Ok(res)
+}
+
+fn from_inner_struct(input: &DeriveInput) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field = get_field(input, "From")?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+        ..
+    } = Details::from_input(&input.ident, field);
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::convert::From<#field_type> for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn from(wrap: #field_type) -> Self {
+                #struct_name {#field_name: wrap}
+            }
+        }
+    })
+}
+
+// The formatting traits display_from accepts, with the format spec used to print the type's own level in a chain.
+const DISPLAY_FROM_TRAITS: &[(&str, &str)] = &[
+    ("Debug", "{:?}"),
+    ("LowerHex", "{:x}"),
+    ("UpperHex", "{:X}"),
+    ("Octal", "{:o}"),
+    ("Binary", "{:b}"),
+    ("LowerExp", "{:e}"),
+    ("UpperExp", "{:E}"),
+    ("Pointer", "{:p}"),
+];
+
+fn display_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let struct_name = &input.ident;
+    let std = std();
+    let display_from = get_meta_value(
+        &input.attrs,
+        "Display",
+        "display_from",
+        Some("#[display_from(Debug)]`"),
+    )?
+    .expect("provided example, should always return a value if succeeded.");
+    let spec = match display_from {
+        Member::Named(ref name) => DISPLAY_FROM_TRAITS
+            .iter()
+            .find(|&&(t, _)| name == t)
+            .map(|&(_, spec)| spec),
+        Member::Unnamed(_) => None,
+    };
+    let spec = match spec {
+        Some(spec) => spec,
+        None => {
+            let traits: Vec<_> = DISPLAY_FROM_TRAITS.iter().map(|&(t, _)| t).collect();
+            return Err(Error::new_spanned(
+                &display_from,
+                format!(
+                    "derive_wrapper: display_from supports only the formatting traits: {}",
+                    traits.join(", ")
+                ),
+            ));
+        }
+    };
+
+    let options = ErrorAttr::from_attrs(&input.attrs)?.unwrap_or_default();
+    let body = match options.chain {
+        Some(ref separator) => {
+            let chain = generate_chain(&options.error_trait(), separator);
+            quote! {
+                if f.alternate() {
+                    write!(f, #spec, self)?;
+                } else {
+                    #std::fmt::#display_from::fmt(&self, f)?;
+                }
+                #chain
+            }
+        }
+        None => quote!(#std::fmt::#display_from::fmt(&self, f)),
+    };
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Display for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                #body
+            }
+        }
+    })
+}
+
+// With the alternate flag, appends the causes returned by source() after the type's own message.
+// The walk stops after `MAX_DEPTH` causes so a cyclic chain can't loop forever.
+fn generate_chain(error_trait: &TokenStream2, separator: &LitStr) -> TokenStream2 {
+    let std = std();
+    quote! {
+        if f.alternate() {
+            const MAX_DEPTH: usize = 32;
+            let mut source = #error_trait::source(self);
+            let mut depth = 0;
+            while let #std::option::Option::Some(cause) = source {
+                f.write_str(#separator)?;
+                if depth == MAX_DEPTH {
+                    return f.write_str("...");
+                }
+                write!(f, "{}", cause)?;
+                source = #error_trait::source(cause);
+                depth += 1;
+            }
+        }
+        #std::result::Result::Ok(())
+    }
+}
+
+struct Radix {
+    trait_name: &'static str,
+    prefix: &'static str,
+    table: &'static str,
+    bits: usize,
+    per_byte: usize,
+    // Formats a single element zero padded to the width given as the second argument.
+    padded_format: &'static str,
+}
+
+const LOWER_HEX: Radix = Radix {
+    trait_name: "LowerHex",
+    prefix: "0x",
+    table: "0123456789abcdef",
+    bits: 4,
+    per_byte: 2,
+    padded_format: "{:01$x}",
+};
+
+const UPPER_HEX: Radix = Radix {
+    trait_name: "UpperHex",
+    prefix: "0x",
+    table: "0123456789ABCDEF",
+    bits: 4,
+    per_byte: 2,
+    padded_format: "{:01$X}",
+};
+
+const OCTAL: Radix = Radix {
+    trait_name: "Octal",
+    prefix: "0o",
+    table: "01234567",
+    bits: 3,
+    per_byte: 3,
+    padded_format: "{:01$o}",
+};
+
+const BINARY: Radix = Radix {
+    trait_name: "Binary",
+    prefix: "0b",
+    table: "01",
+    bits: 1,
+    per_byte: 8,
+    padded_format: "{:01$b}",
+};
+
+fn fmt_iter_inner(input: DeriveInput, radix: &Radix) -> Result<TokenStream2> {
+    let field = get_field(&input, &format!("{}Iter", radix.trait_name))?;
+    let options = HexOptions::from_attrs(&input.attrs)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let trait_name = Ident::new(radix.trait_name, Span::call_site());
+    let per_byte = radix.per_byte;
+
+    let consts = options.generate_consts();
+    let (separator_init, count_separator) =
+        options.generate_separator(&std, quote!(separators += 1;));
+    let separators_len = if options.separator.is_some() {
+        quote! {
+            let mut separators = 0;
+            {
+                let mut left = digits;
+                #separator_init
+                while left > 0 {
+                    #count_separator
+                    left = left.saturating_sub(unit_len * PER_BYTE);
+                }
+            }
+            let len = len + separators * SEPARATOR.chars().count();
+        }
+    } else {
+        quote!()
+    };
+
+    let unit_len = options.generate_unit_len(field_type, &field_name);
+    let body = generate_digits(radix, &options, field_type, &field_name);
+    let padded = generate_padded_fmt(&std, radix.prefix, body);
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::#trait_name for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                const PER_BYTE: usize = #per_byte;
+                #consts
+                let unit_len = #unit_len;
+                let total = self.#field_name.iter().count() * unit_len * PER_BYTE;
+                let digits = f.precision().map_or(total, |p| #std::cmp::min(p, total));
+                let len = digits;
+                #separators_len
+                #padded
+            }
+        }
+    })
+}
+
+// Writes the first `digits` digits of the field's bytes, with the separators of `options`.
+// The generated code expects `digits`, `PER_BYTE` and the consts of `options` to be in scope.
+fn generate_digits(
+    radix: &Radix,
+    options: &HexOptions,
+    field_type: &Type,
+    field_name: &TokenStream2,
+) -> TokenStream2 {
+    let std = std();
+    if !options.encodes_bytes(field_type) {
+        return generate_formatted_digits(radix, options, field_name);
+    }
+    let bits = radix.bits;
+    let table = LitByteStr::new(radix.table.as_bytes(), Span::call_site());
+    let flush = quote! {
+        f.write_str(#std::str::from_utf8(&buf[..filled]).map_err(|_| #std::fmt::Error)?)?;
+        filled = 0;
+    };
+    let (separator_init, write_separator) = options.generate_separator(
+        &std,
+        quote! {
+            if buf.len() - filled < SEPARATOR.len() {
+                #flush
+            }
+            if SEPARATOR.len() > buf.len() {
+                f.write_str(SEPARATOR)?;
+            } else {
+                buf[filled..filled + SEPARATOR.len()].copy_from_slice(SEPARATOR.as_bytes());
+                filled += SEPARATOR.len();
+            }
+        },
+    );
+    let unit = options.generate_unit(quote!(ch));
+    // Digits are encoded with a lookup table into a stack buffer which is written once per chunk.
+    quote! {
+        const TABLE: &[u8] = #table;
+        const MASK: u8 = (1 << #bits) - 1;
+        let mut buf = [0u8; 64];
+        let mut filled = 0;
+        let mut left = digits;
+        #separator_init
+        for ch in self.#field_name.iter() {
+            if left == 0 {
+                break;
+            }
+            #write_separator
+            for &byte in #unit.iter() {
+                let byte: u8 = byte;
+                if buf.len() - filled < PER_BYTE {
+                    #flush
+                }
+                let mut shift = #bits * PER_BYTE;
+                for _ in 0..#std::cmp::min(left, PER_BYTE) {
+                    shift -= #bits;
+                    buf[filled] = TABLE[((byte >> shift) & MASK) as usize];
+                    filled += 1;
+                }
+                left = left.saturating_sub(PER_BYTE);
+                if left == 0 {
+                    break;
+                }
+            }
+        }
+        f.write_str(#std::str::from_utf8(&buf[..filled]).map_err(|_| #std::fmt::Error)?)?;
+    }
+}
+
+fn debug_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let example = "#[debug(transparent)]` or `#[debug(hex)]` or `#[debug(display)]";
+    let mut mode = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("debug"))
+    {
+        let mv = find_meta_value(attr, "debug", example)?;
+        if mv.found {
+            if mode.is_some() || mv.multiple() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "derive_wrapper: supports only a single debug mode",
+                ));
+            }
+            mode = Some(mv.get_first_name().ok_or_else(|| {
+                Error::new_spanned(
+                    attr,
+                    format!("derive_wrapper: missing debug mode. Try: `{}`", example),
+                )
+            })?);
+        }
+    }
+    let mode = mode.ok_or_else(|| {
+        Error::new_spanned(
+            &input,
+            format!(
+                "Deriving WrapperDebug requires a debug attribute. Try: `{}`",
+                example
+            ),
+        )
+    })?;
+
+    let field = get_field(&input, "WrapperDebug")?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match mode.as_str() {
+        "transparent" => quote!(#std::fmt::Debug::fmt(&self.#field_name, f)),
+        "display" => quote!(#std::fmt::Display::fmt(self, f)),
+        "hex" => {
+            let options = HexOptions::from_attrs(&input.attrs)?;
+            let consts = options.generate_consts();
+            let unit_len = options.generate_unit_len(field_type, &field_name);
+            let digits = generate_digits(&LOWER_HEX, &options, field_type, &field_name);
+            let per_byte = LOWER_HEX.per_byte;
+            let open = format!("{}(", struct_name);
+            quote! {
+                const PER_BYTE: usize = #per_byte;
+                #consts
+                let unit_len = #unit_len;
+                let digits = self.#field_name.iter().count() * unit_len * PER_BYTE;
+                f.write_str(#open)?;
+                #digits
+                f.write_str(")")
+            }
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                format!(
+                    "derive_wrapper: unknown debug mode `{}`. Try: `{}`",
+                    mode, example
+                ),
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Debug for #struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                #body
+            }
+        }
+    })
+}
+
+enum Fingerprint {
+    Hex,
+    Len,
+}
+
+#[derive(Default)]
+struct RedactedOptions {
+    placeholder: Option<LitStr>,
+    display: bool,
+    fingerprint: Option<Fingerprint>,
+    allow_reveal: bool,
+}
+
+impl Parse for RedactedOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let mut options = RedactedOptions::default();
+        while !content.is_empty() {
+            let name: Ident = content.parse()?;
+            if name == "placeholder" {
+                content.parse::<Token![=]>()?;
+                options.placeholder = Some(content.parse()?);
+            } else if name == "fingerprint" {
+                content.parse::<Token![=]>()?;
+                let kind: LitStr = content.parse()?;
+                options.fingerprint = Some(match kind.value().as_str() {
+                    "hex" => Fingerprint::Hex,
+                    "len" => Fingerprint::Len,
+                    _ => {
+                        return Err(Error::new(
+                            kind.span(),
+                            "derive_wrapper: redacted fingerprint must be \"hex\" or \"len\"",
+                        ))
+                    }
+                });
+            } else if name == "display" {
+                options.display = true;
+            } else if name == "allow_reveal" {
+                options.allow_reveal = true;
+            } else {
+                return Err(Error::new(name.span(), format!("derive_wrapper: unknown redacted option `{}`. Try: `#[redacted(placeholder = \"***\", fingerprint = \"hex\", display)]`", name)));
+            }
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl RedactedOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut res = None;
+        for attr in attrs {
+            if attr.path.is_ident("redacted") {
+                if res.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: supports only a single redacted attribute",
+                    ));
+                }
+                res = Some(syn::parse2(attr.tts.clone())?);
+            }
+        }
+        Ok(res.unwrap_or_default())
+    }
+}
+
+const REVEALING_DERIVES: &[&str] = &[
+    "LowerHex",
+    "UpperHex",
+    "Octal",
+    "Binary",
+    "LowerHexIter",
+    "UpperHexIter",
+    "OctalIter",
+    "BinaryIter",
+];
+
+// Debug, and optionally Display, that never print the wrapped secret.
+// Unless `allow_reveal` is set, the type must not implement Display or the hex, octal and binary
+// formatting traits, which would print it.
+fn redacted_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let options = RedactedOptions::from_attrs(&input.attrs)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let struct_name = &input.ident;
+    let std = std();
+    let placeholder = options
+        .placeholder
+        .clone()
+        .unwrap_or_else(|| LitStr::new("<redacted>", Span::call_site()));
+
+    if !options.allow_reveal {
+        if let Some(attr) = input
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("display_from"))
+        {
+            return Err(Error::new_spanned(
+                attr,
+                "derive_wrapper: a Redacted type shouldn't derive Display, it would print the secret. Try: `#[redacted(display)]` or `#[redacted(allow_reveal)]`",
+            ));
+        }
+        // Only the derives listed after Redacted are visible here, the assertions below catch the rest.
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("derive"))
+        {
+            let derives = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list.nested,
+                _ => continue,
+            };
+            for derive in derives {
+                if let NestedMeta::Meta(Meta::Word(ref name)) = derive {
+                    if REVEALING_DERIVES.iter().any(|revealing| name == revealing) {
+                        return Err(Error::new_spanned(
+                            name,
+                            format!("derive_wrapper: a Redacted type shouldn't derive {}, it would print the secret. Try: `#[redacted(allow_reveal)]`", name),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let fingerprint = match options.fingerprint {
+        Some(ref fingerprint) => {
+            let field = get_field(&input, "Redacted")?;
+            let field_name = Details::from_input(struct_name, field).field_name;
+            let bytes = quote!(#std::convert::AsRef::<[u8]>::as_ref(&self.#field_name));
+            match *fingerprint {
+                Fingerprint::Hex => quote! {
+                    f.write_str(" ")?;
+                    for byte in #bytes.iter().take(4) {
+                        write!(f, "{:02x}", byte)?;
+                    }
+                    f.write_str("..")?;
+                },
+                Fingerprint::Len => quote! {
+                    write!(f, ", {} bytes", #bytes.len())?;
+                },
+            }
+        }
+        None => quote!(),
+    };
+    let open = format!("{}(", struct_name);
+
+    let display = if options.display {
+        quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::fmt::Display for #struct_name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                    f.write_str(#placeholder)?;
+                    #fingerprint
+                    #std::result::Result::Ok(())
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // If the type implements a forbidden trait, the blanket impls below make `check` ambiguous,
+    // and the compile error names the trait.
+    let assertions = if options.allow_reveal {
+        quote!()
+    } else {
+        let mut forbidden = vec!["LowerHex", "UpperHex", "Octal", "Binary"];
+        if !options.display {
+            forbidden.push("Display");
+        }
+        let checks = forbidden.into_iter().map(|trait_name| {
+            let check = Ident::new(
+                &format!("Redacted{}MustNotImplement{}", struct_name, trait_name),
+                Span::call_site(),
+            );
+            let trait_name = Ident::new(trait_name, Span::call_site());
+            quote! {
+                {
+                    trait #check<A> {
+                        fn check() {}
+                    }
+                    impl<T: ?Sized> #check<()> for T {}
+                    struct Forbidden;
+                    impl<T: ?Sized + #std::fmt::#trait_name> #check<Forbidden> for T {}
+                    let _ = <#struct_name #ty_generics as #check<_>>::check;
+                }
+            }
+        });
+        quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #[allow(dead_code)]
+                fn __redacted_assertions() {
+                    #(#checks)*
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Debug for #struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                f.write_str(#open)?;
+                f.write_str(#placeholder)?;
+                #fingerprint
+                f.write_str(")")
+            }
+        }
+
+        #display
+        #assertions
+    })
+}
+
+// Writes the first `digits` digits of the field's elements, each formatted with the radix's trait
+// and zero padded to `unit_len * PER_BYTE` digits, for elements that aren't encoded as bytes.
+fn generate_formatted_digits(
+    radix: &Radix,
+    options: &HexOptions,
+    field_name: &TokenStream2,
+) -> TokenStream2 {
+    let std = std();
+    let format = radix.padded_format;
+    let (separator_init, write_separator) =
+        options.generate_separator(&std, quote!(out.f.write_str(SEPARATOR)?;));
+    quote! {
+        struct Limit<'a, 'b: 'a> {
+            f: &'a mut #std::fmt::Formatter<'b>,
+            left: usize,
+        }
+        impl<'a, 'b: 'a> #std::fmt::Write for Limit<'a, 'b> {
+            fn write_str(&mut self, s: &str) -> #std::fmt::Result {
+                // Digits are ASCII, so any prefix is valid.
+                let len = #std::cmp::min(self.left, s.len());
+                self.left -= len;
+                self.f.write_str(&s[..len])
+            }
+        }
+        let mut out = Limit { f: &mut *f, left: digits };
+        #separator_init
+        for ch in self.#field_name.iter() {
+            if out.left == 0 {
+                break;
+            }
+            #write_separator
+            #std::fmt::Write::write_fmt(&mut out, format_args!(#format, ch, unit_len * PER_BYTE))?;
+        }
+    }
+}
+
+fn fromhexiter_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let field = get_field(&input, "FromHexIter")?;
+    let options = HexOptions::from_attrs(&input.attrs)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let vis = &input.vis;
+    let error_name = Ident::new(&format!("Parse{}Error", struct_name), Span::call_site());
+    let error_doc = format!(
+        "An error returned when parsing a `{}` from hex",
+        struct_name
+    );
+
+    let consts = options.generate_consts();
+    let (separator_init, read_separator) = options.generate_separator(
+        &std,
+        quote! {
+            if !s[pos..].starts_with(SEPARATOR.as_bytes()) {
+                return #std::result::Result::Err(if pos == s.len() {
+                    #error_name::InvalidLength
+                } else {
+                    #error_name::InvalidSeparator(pos)
+                });
+            }
+            pos += SEPARATOR.len();
+        },
+    );
+    let read_byte = quote! {
+        let byte = digit(s, pos)? << 4 | digit(s, pos + 1)?;
+        pos += 2;
+    };
+
+    let (parse, wrapped) = if let Type::Array(ref array) = *field_type {
+        let array_len = &array.len;
+        let parse = if let Some((ref to_bytes, ref from_bytes)) = options.element {
+            let elem = &array.elem;
+            quote! {
+                let mut out: #field_type = [0; #array_len];
+                for slot in out.iter_mut() {
+                    #read_separator
+                    let mut bytes = slot.#to_bytes();
+                    for slot in bytes.iter_mut() {
+                        #read_byte
+                        *slot = byte;
+                    }
+                    *slot = <#elem>::#from_bytes(bytes);
+                }
+            }
+        } else if is_u8(&array.elem) {
+            quote! {
+                let mut out = [0u8; #array_len];
+                for slot in out.iter_mut() {
+                    #read_separator
+                    #read_byte
+                    *slot = byte;
+                }
+            }
+        } else if primitive_integer(&array.elem).is_some() {
+            // Wider integers are read big-endian, the way the hex formatting derives print them.
+            let elem = &array.elem;
+            quote! {
+                let mut out: #field_type = [0; #array_len];
+                for slot in out.iter_mut() {
+                    #read_separator
+                    for _ in 0..#std::mem::size_of::<#elem>() {
+                        #read_byte
+                        *slot = slot.wrapping_shl(8) | byte as #elem;
+                    }
+                }
+            }
+        } else {
+            return Err(Error::new_spanned(
+                &array.elem,
+                "derive_wrapper: FromHexIter supports only arrays of primitive integers",
+            ));
+        };
+        (parse, quote!(out))
+    } else if options.element.is_some() {
+        return Err(Error::new_spanned(
+            field_type,
+            "derive_wrapper: FromHexIter supports the hex element option only for array fields",
+        ));
+    } else if !cfg!(feature = "std") {
+        return Err(Error::new_spanned(
+            field_type,
+            "derive_wrapper: FromHexIter supports only array fields without the std feature",
+        ));
+    } else {
+        if let Some(elem) = iter_element(field_type) {
+            if !is_u8(&elem) {
+                return Err(Error::new_spanned(
+                    field_type,
+                    "derive_wrapper: FromHexIter supports wider elements only for array fields",
+                ));
+            }
+        }
+        let parse = quote! {
+            let mut out = #std::vec::Vec::with_capacity(s.len() / 2);
+            while pos < s.len() {
+                #read_separator
+                #read_byte
+                out.push(byte);
+            }
+        };
+        (parse, quote!(#std::convert::From::from(out)))
+    };
+    let other_fields = get_other_fields(&input, field)
+        .into_iter()
+        .map(|name| quote!(#name: #std::default::Default::default()));
+
+    Ok(quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis enum #error_name {
+            /// The string has too few or too many hex digits
+            InvalidLength,
+            /// The character at the given byte position isn't a hex digit
+            InvalidDigit(usize),
+            /// The separator is missing at the given byte position
+            InvalidSeparator(usize),
+        }
+
+        #[allow(unused_qualifications)]
+        impl #std::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                match *self {
+                    #error_name::InvalidLength => f.write_str("invalid hex string length"),
+                    #error_name::InvalidDigit(pos) => write!(f, "invalid hex digit at position {}", pos),
+                    #error_name::InvalidSeparator(pos) => write!(f, "expected separator at position {}", pos),
+                }
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #std::error::Error for #error_name {
+            // Required before Rust 1.42, which added the default.
+            #[inline]
+            fn description(&self) -> &str {
+                "description() is deprecated; use Display"
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::str::FromStr for #struct_name #ty_generics #where_clause {
+            type Err = #error_name;
+
+            fn from_str(s: &str) -> #std::result::Result<Self, Self::Err> {
+                fn digit(s: &[u8], pos: usize) -> #std::result::Result<u8, #error_name> {
+                    match s.get(pos) {
+                        #std::option::Option::Some(&c) => (c as char)
+                            .to_digit(16)
+                            .map(|d| d as u8)
+                            .ok_or(#error_name::InvalidDigit(pos)),
+                        #std::option::Option::None => #std::result::Result::Err(#error_name::InvalidLength),
+                    }
+                }
+
+                #consts
+                let s = s.as_bytes();
+                let mut pos = if s.starts_with(b"0x") || s.starts_with(b"0X") { 2 } else { 0 };
+                #separator_init
+                #parse
+                if pos != s.len() {
+                    return #std::result::Result::Err(#error_name::InvalidLength);
+                }
+                #std::result::Result::Ok(#struct_name {
+                    #field_name: #wrapped,
+                    #(#other_fields,)*
+                })
+            }
+        }
+    })
+}
+
+// Surrounds `body` with the alternate prefix and the formatter's width, fill and alignment.
+// The generated code expects `len`, the number of chars written by `body`, to be in scope.
+fn generate_padded_fmt(std: &Path, prefix: &str, body: TokenStream2) -> TokenStream2 {
+    quote! {
+        let prefix = if f.alternate() { #prefix } else { "" };
+        let padding = f.width().map_or(0, |width| width.saturating_sub(prefix.len() + len));
+        let (pre, post) = if f.sign_aware_zero_pad() {
+            (0, 0)
+        } else {
+            match f.align() {
+                #std::option::Option::Some(#std::fmt::Alignment::Left) => (0, padding),
+                #std::option::Option::Some(#std::fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+                _ => (padding, 0),
+            }
+        };
+        let fill = f.fill();
+        for _ in 0..pre {
+            #std::fmt::Write::write_char(f, fill)?;
+        }
+        f.write_str(prefix)?;
+        if f.sign_aware_zero_pad() {
+            for _ in 0..padding {
+                #std::fmt::Write::write_char(f, '0')?;
+            }
+        }
+        #body
+        for _ in 0..post {
+            #std::fmt::Write::write_char(f, fill)?;
+        }
+        #std::result::Result::Ok(())
+    }
+}
+
+fn fmt_inner(input: DeriveInput, trait_name: &str) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        std,
+        ..
+    } = Details::from_input(&input.ident, field);
+    let trait_name = Ident::new(trait_name, Span::call_site());
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::#trait_name for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                #std::fmt::#trait_name::fmt(&self.#field_name, f)
+            }
+        }
+    })
+}
+
+#[allow(non_snake_case)]
+fn generate_index_from_T(
+    output: Option<TokenStream2>,
+    T: TokenStream2,
+    input: &DeriveInput,
+    field: &Field,
+) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let field_type = array_to_slice(field_type.clone());
+    let output = output.unwrap_or_else(|| quote!(<#field_type as #std::ops::Index<#T>>::Output));
+    quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::ops::Index<#T> for #struct_name #ty_generics #where_clause {
+            type Output = #output;
+            #[inline]
+            fn index(&self, index: #T) -> &Self::Output {
+                &self.#field_name[index]
+            }
+        }
+    }
+}
+
+fn index_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let field = get_field(&input, "Index")?;
+    let index_output = get_meta_value(&input.attrs, "Index", "index_output", None)?
+        .map(ToTokens::into_token_stream);
+    let std = std();
+
+    let slice_output = index_output.clone().map(|index| quote!([#index]));
+
+    let index_usize = generate_index_from_T(index_output, quote!(usize), &input, field);
+    let index_range_usize = generate_index_from_T(
+        slice_output.clone(),
+        quote!(#std::ops::Range<usize>),
+        &input,
+        field,
+    );
+    let index_range_to_usize = generate_index_from_T(
+        slice_output.clone(),
+        quote!(#std::ops::RangeTo<usize>),
+        &input,
+        field,
+    );
+    let index_range_from_usize = generate_index_from_T(
+        slice_output.clone(),
+        quote!(#std::ops::RangeFrom<usize>),
+        &input,
+        field,
+    );
+    let index_range_full =
+        generate_index_from_T(slice_output, quote!(#std::ops::RangeFull), &input, field);
+
+    Ok(quote! {
+        #index_usize
+        #index_range_usize
+        #index_range_to_usize
+        #index_range_from_usize
+        #index_range_full
+    })
+}
+
+// The input's generics with an extra lifetime, for impls on `&'lifetime Struct`.
+// The lifetime is named so it can't clash with the struct's own lifetimes.
+fn generics_with_lifetime(generics: &Generics) -> (Generics, Lifetime) {
+    let mut generics = generics.clone();
+    let lifetime = Lifetime::new("'__derive_wrapper", Span::call_site());
+    generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())),
+    );
+    (generics, lifetime)
+}
+
+// Builds the struct back from `value` for the wrapped field,
+// copying the rest of the fields from `this` (cloning them if `this` is a reference).
+fn rebuild_struct(
+    input: &DeriveInput,
+    field: &Field,
+    value: TokenStream2,
+    this: TokenStream2,
+    by_ref: bool,
+) -> TokenStream2 {
+    let std = std();
+    let Details {
+        struct_name,
+        field_name,
+        ..
+    } = Details::from_input(&input.ident, field);
+    let others = get_other_fields(input, field).into_iter().map(|other| {
+        if by_ref {
+            quote!(#other: #std::clone::Clone::clone(&#this.#other))
+        } else {
+            quote!(#other: #this.#other)
+        }
+    });
+    quote!(#struct_name { #field_name: #value, #(#others,)* })
+}
+
+fn binary_op_inner(input: DeriveInput, trait_name: &str, method: &str) -> Result<TokenStream2> {
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        std,
+        ..
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (ref_generics, lifetime) = generics_with_lifetime(&input.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let trait_name = Ident::new(trait_name, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+    let this = quote!(#struct_name #ty_generics);
+
+    let impls = [(false, false), (false, true), (true, false), (true, true)]
+        .iter()
+        .map(|&(self_ref, rhs_ref)| {
+            let generics = if self_ref || rhs_ref {
+                &ref_impl_generics
+            } else {
+                &impl_generics
+            };
+            let lhs_ty = if self_ref {
+                quote!(&#lifetime #this)
+            } else {
+                quote!(#this)
+            };
+            let rhs_ty = if rhs_ref {
+                quote!(&#lifetime #this)
+            } else {
+                quote!(#this)
+            };
+            let lhs = if self_ref {
+                quote!(&self.#field_name)
+            } else {
+                quote!(self.#field_name)
+            };
+            let rhs = if rhs_ref {
+                quote!(&rhs.#field_name)
+            } else {
+                quote!(rhs.#field_name)
+            };
+            let value = quote!(#std::ops::#trait_name::#method(#lhs, #rhs));
+            let output = rebuild_struct(&input, field, value, quote!(self), self_ref);
+            quote! {
+                #[allow(unused_qualifications)]
+                impl #generics #std::ops::#trait_name<#rhs_ty> for #lhs_ty #where_clause {
+                    type Output = #this;
+                    #[inline]
+                    fn #method(self, rhs: #rhs_ty) -> Self::Output {
+                        #output
+                    }
+                }
+            }
+        });
+
+    Ok(quote!(#(#impls)*))
+}
+
+fn unary_op_inner(input: DeriveInput, trait_name: &str, method: &str) -> Result<TokenStream2> {
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        std,
+        ..
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (ref_generics, lifetime) = generics_with_lifetime(&input.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let trait_name = Ident::new(trait_name, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+    let this = quote!(#struct_name #ty_generics);
+
+    let by_value = rebuild_struct(
+        &input,
+        field,
+        quote!(#std::ops::#trait_name::#method(self.#field_name)),
+        quote!(self),
+        false,
+    );
+    let by_ref = rebuild_struct(
+        &input,
+        field,
+        quote!(#std::ops::#trait_name::#method(&self.#field_name)),
+        quote!(self),
+        true,
+    );
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::ops::#trait_name for #this #where_clause {
+            type Output = #this;
+            #[inline]
+            fn #method(self) -> Self::Output {
+                #by_value
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #ref_impl_generics #std::ops::#trait_name for &#lifetime #this #where_clause {
+            type Output = #this;
+            #[inline]
+            fn #method(self) -> Self::Output {
+                #by_ref
+            }
+        }
+    })
+}
+
+// Shifts take the number of bits as a `u32`, like the shift methods of the primitive integers.
+fn shift_inner(input: DeriveInput, trait_name: &str, method: &str) -> Result<TokenStream2> {
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        std,
+        ..
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (ref_generics, lifetime) = generics_with_lifetime(&input.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let trait_name = Ident::new(trait_name, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+    let this = quote!(#struct_name #ty_generics);
+
+    let by_value = rebuild_struct(
+        &input,
+        field,
+        quote!(#std::ops::#trait_name::#method(self.#field_name, rhs)),
+        quote!(self),
+        false,
+    );
+    let by_ref = rebuild_struct(
+        &input,
+        field,
+        quote!(#std::ops::#trait_name::#method(&self.#field_name, rhs)),
+        quote!(self),
+        true,
+    );
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::ops::#trait_name<u32> for #this #where_clause {
+            type Output = #this;
+            #[inline]
+            fn #method(self, rhs: u32) -> Self::Output {
+                #by_value
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #ref_impl_generics #std::ops::#trait_name<u32> for &#lifetime #this #where_clause {
+            type Output = #this;
+            #[inline]
+            fn #method(self, rhs: u32) -> Self::Output {
+                #by_ref
+            }
+        }
+    })
+}
+
+// A single `NAME = value` entry of the flags attribute.
+struct FlagSpec {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for FlagSpec {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(FlagSpec { name, value })
+    }
+}
+
+struct FlagsAttr(Vec<FlagSpec>);
+
+impl Parse for FlagsAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let specs: Punctuated<FlagSpec, Token![,]> = Punctuated::parse_terminated(&content)?;
+        let specs: Vec<_> = specs.into_iter().collect();
+        for (i, spec) in specs.iter().enumerate() {
+            if specs[..i].iter().any(|other| other.name == spec.name) {
+                return Err(Error::new_spanned(
+                    &spec.name,
+                    format!("derive_wrapper: the flag `{}` is defined twice", spec.name),
+                ));
+            }
+        }
+        Ok(FlagsAttr(specs))
+    }
+}
+
+impl FlagsAttr {
+    pub fn from_attrs(input: &DeriveInput) -> Result<Self> {
+        let mut res = None;
+        for attr in &input.attrs {
+            if attr.path.is_ident("flags") {
+                if res.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: supports only a single flags attribute",
+                    ));
+                }
+                res = Some(syn::parse2(attr.tts.clone())?);
+            }
+        }
+        res.ok_or_else(|| {
+            Error::new_spanned(
+                input,
+                "Deriving Flags requires a flags attribute. Try: `#[flags(FIRST = 1, SECOND = 2)]`",
+            )
+        })
+    }
+}
+
+fn flags_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let FlagsAttr(specs) = FlagsAttr::from_attrs(&input)?;
+    let field = get_field(&input, "Flags")?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    if primitive_integer(field_type).is_none() {
+        return Err(Error::new_spanned(
+            field_type,
+            format!(
+                "Deriving Flags requires a primitive integer field. Supported types: {}",
+                PRIMITIVE_INTEGERS.join(", ")
+            ),
+        ));
+    }
+    let others = constant_other_fields(&input, field, "flags")?;
+    let others = &others;
+    let struct_name_str = struct_name.to_string();
+
+    let consts = specs.iter().map(|spec| {
+        let name = &spec.name;
+        let value = &spec.value;
+        quote!(pub const #name: Self = #struct_name { #field_name: #value, #(#others,)* };)
+    });
+    let names = specs.iter().map(|spec| {
+        let name = &spec.name;
+        let name_str = name.to_string();
+        quote!((#name_str, Self::#name.#field_name))
+    });
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #(#consts)*
+
+            /// Whether all the bits of `other` are set.
+            #[inline]
+            pub fn contains(&self, other: Self) -> bool {
+                self.#field_name & other.#field_name == other.#field_name
+            }
+
+            /// Sets the bits of `other`.
+            #[inline]
+            pub fn insert(&mut self, other: Self) {
+                self.#field_name |= other.#field_name;
+            }
+
+            /// Clears the bits of `other`.
+            #[inline]
+            pub fn remove(&mut self, other: Self) {
+                self.#field_name &= !other.#field_name;
+            }
+
+            /// The names and values of the named flags that are set.
+            pub fn iter_names(&self) -> impl #std::iter::Iterator<Item = (&'static str, Self)> {
+                let bits = self.#field_name;
+                let names: &'static [(&'static str, #field_type)] = &[#(#names),*];
+                names
+                    .iter()
+                    .filter(move |flag| flag.1 != 0 && bits & flag.1 == flag.1)
+                    .map(|flag| (flag.0, #struct_name { #field_name: flag.1, #(#others,)* }))
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Display for #struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                let mut unknown = self.#field_name;
+                let mut first = true;
+                for (name, flag) in self.iter_names() {
+                    if !first {
+                        f.write_str(" | ")?;
+                    }
+                    f.write_str(name)?;
+                    unknown &= !flag.#field_name;
+                    first = false;
+                }
+                if unknown != 0 {
+                    if !first {
+                        f.write_str(" | ")?;
+                    }
+                    write!(f, "{:#x}", unknown)?;
+                } else if first {
+                    f.write_str("0x0")?;
+                }
+                #std::result::Result::Ok(())
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Debug for #struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                write!(f, "{}({})", #struct_name_str, self)
+            }
+        }
+    })
+}
+
+fn fold_inner(input: DeriveInput, trait_name: &str, method: &str) -> Result<TokenStream2> {
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (ref_generics, lifetime) = generics_with_lifetime(&input.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let trait_name = Ident::new(trait_name, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+    let this = quote!(#struct_name #ty_generics);
+
+    let mut fold_inner_type = false;
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("fold")) {
+        let options = find_meta_value(attr, "fold", "#[fold(inner)]")?;
+        for option in options.name {
+            match option {
+                Member::Named(ref name) if name == "inner" => fold_inner_type = true,
+                _ => {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: unknown fold option. Try: `#[fold(inner)]`",
+                    ))
+                }
+            }
+        }
+    }
+
+    let others: Vec<_> = get_other_fields(&input, field)
+        .into_iter()
+        .map(|other| quote!(#other: #std::default::Default::default()))
+        .collect();
+    let others = &others;
+    let wrap = |value: TokenStream2| quote!(#struct_name { #field_name: #value, #(#others,)* });
+    let by_value = wrap(quote! {
+        <#field_type as #std::iter::#trait_name>::#method(iter.map(|item| item.#field_name))
+    });
+    let by_ref = wrap(quote! {
+        <#field_type as #std::iter::#trait_name<&#lifetime #field_type>>::#method(iter.map(|item| &item.#field_name))
+    });
+    let inner = if fold_inner_type {
+        let from_inner = wrap(quote!(<#field_type as #std::iter::#trait_name>::#method(iter)));
+        quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::iter::#trait_name<#field_type> for #this #where_clause {
+                #[inline]
+                fn #method<I: #std::iter::Iterator<Item = #field_type>>(iter: I) -> Self {
+                    #from_inner
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::iter::#trait_name for #this #where_clause {
+            #[inline]
+            fn #method<I: #std::iter::Iterator<Item = Self>>(iter: I) -> Self {
+                #by_value
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #ref_impl_generics #std::iter::#trait_name<&#lifetime #this> for #this #where_clause {
+            #[inline]
+            fn #method<I: #std::iter::Iterator<Item = &#lifetime Self>>(iter: I) -> Self {
+                #by_ref
+            }
+        }
+
+        #inner
+    })
+}
+
+// `TryFrom<&[u8]>` reads little endian bytes unless `#[byte_order(be)]` is specified.
+fn byte_order_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let field = get_field(&input, "ByteOrder")?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (ref_generics, lifetime) = generics_with_lifetime(&input.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let integer = primitive_integer(field_type).ok_or_else(|| {
+        Error::new_spanned(
+            field_type,
+            format!(
+                "Deriving ByteOrder requires a primitive integer field. Supported types: {}",
+                PRIMITIVE_INTEGERS.join(", ")
+            ),
+        )
+    })?;
+
+    let mut from_slice = Ident::new("from_le_bytes", Span::call_site());
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("byte_order"))
+    {
+        let options = find_meta_value(attr, "byte_order", "#[byte_order(be)]")?;
+        for option in options.name {
+            match option {
+                Member::Named(ref name) if name == "le" || name == "be" => {
+                    from_slice = Ident::new(&format!("from_{}_bytes", name), Span::call_site())
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: byte_order can be either `le` or `be`. Try: `#[byte_order(be)]`",
+                    ))
+                }
+            }
+        }
+    }
+
+    let bytes = quote!([u8; #std::mem::size_of::<#integer>()]);
+    let others: Vec<_> = get_other_fields(&input, field)
+        .into_iter()
+        .map(|other| quote!(#other: #std::default::Default::default()))
+        .collect();
+    let others = &others;
+    let to_methods = ["to_le_bytes", "to_be_bytes", "to_ne_bytes"]
+        .iter()
+        .map(|method| {
+            let method = Ident::new(method, Span::call_site());
+            quote! {
+                #[inline]
+                pub fn #method(&self) -> #bytes {
+                    self.#field_name.#method()
+                }
+            }
+        });
+    let from_methods = ["from_le_bytes", "from_be_bytes", "from_ne_bytes"]
+        .iter()
+        .map(|method| {
+            let method = Ident::new(method, Span::call_site());
+            quote! {
+                #[inline]
+                pub fn #method(bytes: #bytes) -> Self {
+                    #struct_name { #field_name: #integer::#method(bytes), #(#others,)* }
+                }
+            }
+        });
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #(#to_methods)*
+            #(#from_methods)*
+        }
+
+        #[allow(unused_qualifications)]
+        impl #ref_impl_generics #std::convert::TryFrom<&#lifetime [u8]> for #struct_name #ty_generics #where_clause {
+            type Error = #std::array::TryFromSliceError;
+            #[inline]
+            fn try_from(slice: &#lifetime [u8]) -> #std::result::Result<Self, Self::Error> {
+                let bytes = <#bytes as #std::convert::TryFrom<&#lifetime [u8]>>::try_from(slice)?;
+                #std::result::Result::Ok(Self::#from_slice(bytes))
+            }
+        }
+    })
+}
+
+struct DecimalOptions {
+    scale: u32,
+    trim: bool,
+}
+
+impl Parse for DecimalOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let mut scale = None;
+        let mut trim = false;
+        while !content.is_empty() {
+            let name: Ident = content.parse()?;
+            if name == "scale" {
+                content.parse::<Token![=]>()?;
+                let value: LitInt = content.parse()?;
+                // 10^38 is the largest power of ten that fits in a u128.
+                if value.value() > 38 {
+                    return Err(Error::new(
+                        value.span(),
+                        "derive_wrapper: decimal scale can be at most 38",
+                    ));
+                }
+                scale = Some(value.value() as u32);
+            } else if name == "trim" {
+                trim = true;
+            } else {
+                return Err(Error::new(name.span(), format!("derive_wrapper: unknown decimal option `{}`. Try: `#[decimal(scale = 8, trim)]`", name)));
+            }
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        let scale = scale.ok_or_else(|| {
+            Error::new(
+                Span::call_site(),
+                "derive_wrapper: decimal requires a scale. Try: `#[decimal(scale = 8)]`",
+            )
+        })?;
+        Ok(DecimalOptions { scale, trim })
+    }
+}
+
+impl DecimalOptions {
+    pub fn from_attrs(input: &DeriveInput) -> Result<Self> {
+        let mut res = None;
+        for attr in &input.attrs {
+            if attr.path.is_ident("decimal") {
+                if res.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: supports only a single decimal attribute",
+                    ));
+                }
+                res = Some(syn::parse2(attr.tts.clone())?);
+            }
+        }
+        res.ok_or_else(|| {
+            Error::new_spanned(
+                input,
+                "Deriving Decimal requires a decimal attribute. Try: `#[decimal(scale = 8)]`",
+            )
+        })
+    }
+}
+
+// Display and FromStr for an integer holding a fixed-point number with `scale` decimals.
+// All the arithmetic is done on the magnitude as a u128, so no floats are involved.
+fn decimal_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let options = DecimalOptions::from_attrs(&input)?;
+    let field = get_field(&input, "Decimal")?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let integer = primitive_integer(field_type).ok_or_else(|| {
+        Error::new_spanned(
+            field_type,
+            format!(
+                "Deriving Decimal requires a primitive integer field. Supported types: {}",
+                PRIMITIVE_INTEGERS.join(", ")
+            ),
+        )
+    })?;
+    let signed = integer.to_string().starts_with('i');
+    let vis = &input.vis;
+    let error_name = Ident::new(&format!("Parse{}Error", struct_name), Span::call_site());
+    let error_doc = format!(
+        "An error returned when parsing a `{}` from a decimal string",
+        struct_name
+    );
+    let scale = options.scale;
+    let trim = options.trim;
+
+    let (negative, magnitude) = if signed {
+        (
+            quote!(self.#field_name < 0),
+            quote!((self.#field_name as i128).wrapping_neg() as u128),
+        )
+    } else {
+        (quote!(false), quote!(0))
+    };
+    let sign = if signed {
+        quote! {
+            b'-' => (true, 1),
+        }
+    } else {
+        quote!()
+    };
+    let to_inner = if signed {
+        quote! {
+            if negative {
+                if magnitude > #integer::MAX as u128 + 1 {
+                    return #std::result::Result::Err(#error_name::Overflow);
+                }
+                (magnitude as i128).wrapping_neg() as #integer
+            } else {
+                if magnitude > #integer::MAX as u128 {
+                    return #std::result::Result::Err(#error_name::Overflow);
+                }
+                magnitude as #integer
+            }
+        }
+    } else {
+        quote! {
+            if negative || magnitude > #integer::MAX as u128 {
+                return #std::result::Result::Err(#error_name::Overflow);
+            }
+            magnitude as #integer
+        }
+    };
+    let other_fields = get_other_fields(&input, field)
+        .into_iter()
+        .map(|name| quote!(#name: #std::default::Default::default()));
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::fmt::Display for #struct_name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                const SCALE: u32 = #scale;
+                let negative = #negative;
+                let magnitude: u128 = if negative {
+                    #magnitude
+                } else {
+                    self.#field_name as u128
+                };
+                let mut integer = magnitude / 10u128.pow(SCALE);
+                let mut fraction = magnitude % 10u128.pow(SCALE);
+                let mut digits = SCALE;
+                let mut zeros = 0;
+                match f.precision() {
+                    #std::option::Option::Some(precision) if precision < SCALE as usize => {
+                        // Rounds half away from zero.
+                        let cut = 10u128.pow(SCALE - precision as u32);
+                        let rest = fraction % cut;
+                        fraction /= cut;
+                        digits = precision as u32;
+                        if rest >= cut - rest {
+                            fraction += 1;
+                            if fraction == 10u128.pow(digits) {
+                                fraction = 0;
+                                integer += 1;
+                            }
+                        }
+                    }
+                    #std::option::Option::Some(precision) => zeros = precision - SCALE as usize,
+                    #std::option::Option::None if #trim => {
+                        while digits > 0 && fraction % 10 == 0 {
+                            fraction /= 10;
+                            digits -= 1;
+                        }
+                    }
+                    #std::option::Option::None => {}
+                }
+
+                // Filled from the end: 39 integer digits, the point and up to 38 decimals fit,
+                // so only the padding zeros requested by the precision are capped.
+                let mut buf = [b'0'; 128];
+                let mut pos = buf.len();
+                pos -= #std::cmp::min(zeros, pos - 79);
+                for _ in 0..digits {
+                    pos -= 1;
+                    buf[pos] = b'0' + (fraction % 10) as u8;
+                    fraction /= 10;
+                }
+                if digits > 0 || zeros > 0 {
+                    pos -= 1;
+                    buf[pos] = b'.';
+                }
+                loop {
+                    pos -= 1;
+                    buf[pos] = b'0' + (integer % 10) as u8;
+                    integer /= 10;
+                    if integer == 0 {
+                        break;
+                    }
+                }
+                let s = #std::str::from_utf8(&buf[pos..]).map_err(|_| #std::fmt::Error)?;
+                f.pad_integral(!negative, "", s)
+            }
+        }
+
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis enum #error_name {
+            /// The string has no digits before or after the decimal point
+            Empty,
+            /// The character at the given byte position isn't a decimal digit
+            InvalidDigit(usize),
+            /// The string has more decimals than the type's scale
+            TooManyDecimals,
+            /// The number doesn't fit in the type
+            Overflow,
+        }
+
+        #[allow(unused_qualifications)]
+        impl #std::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                match *self {
+                    #error_name::Empty => f.write_str("missing digits in decimal string"),
+                    #error_name::InvalidDigit(pos) => write!(f, "invalid decimal digit at position {}", pos),
+                    #error_name::TooManyDecimals => write!(f, "more than {} decimals", #scale),
+                    #error_name::Overflow => f.write_str("decimal number out of range"),
+                }
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #std::error::Error for #error_name {
+            // Required before Rust 1.42, which added the default.
+            #[inline]
+            fn description(&self) -> &str {
+                "description() is deprecated; use Display"
+            }
+        }
+
+        #[allow(unused_qualifications)]
+        impl #impl_generics #std::str::FromStr for #struct_name #ty_generics #where_clause {
+            type Err = #error_name;
+
+            fn from_str(s: &str) -> #std::result::Result<Self, Self::Err> {
+                const SCALE: u32 = #scale;
+                let s = s.as_bytes();
+                let (negative, start) = match s.first() {
+                    #std::option::Option::Some(&sign) => match sign {
+                        #sign
+                        b'+' => (false, 1),
+                        _ => (false, 0),
+                    },
+                    #std::option::Option::None => (false, 0),
+                };
+                let point = s[start..].iter().position(|&c| c == b'.').map(|pos| start + pos);
+                let integer_end = point.unwrap_or(s.len());
+                let fraction_start = point.map_or(s.len(), |pos| pos + 1);
+                if integer_end == start || fraction_start == s.len() && point.is_some() {
+                    return #std::result::Result::Err(#error_name::Empty);
+                }
+                if s.len() - fraction_start > SCALE as usize {
+                    return #std::result::Result::Err(#error_name::TooManyDecimals);
+                }
+
+                let mut magnitude: u128 = 0;
+                for pos in (start..integer_end).chain(fraction_start..s.len()) {
+                    let digit = match s[pos] {
+                        c @ b'0'..=b'9' => u128::from(c - b'0'),
+                        _ => return #std::result::Result::Err(#error_name::InvalidDigit(pos)),
+                    };
+                    magnitude = magnitude
+                        .checked_mul(10)
+                        .and_then(|magnitude| magnitude.checked_add(digit))
+                        .ok_or(#error_name::Overflow)?;
+                }
+                let missing = SCALE - (s.len() - fraction_start) as u32;
+                let magnitude = magnitude
+                    .checked_mul(10u128.pow(missing))
+                    .ok_or(#error_name::Overflow)?;
+
+                #std::result::Result::Ok(#struct_name {
+                    #field_name: { #to_inner },
+                    #(#other_fields,)*
+                })
+            }
+        }
+    })
+}
+
+// The projection in `#[wrap(eq_by = path)]`, a function from a reference to the field to the value compared.
+struct EqBy(Path);
+
+impl Parse for EqBy {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let name: Ident = content.parse()?;
+        if name != "eq_by" {
+            return Err(Error::new(name.span(), "expected `eq_by`"));
+        }
+        content.parse::<Token![=]>()?;
+        let path = content.parse()?;
+        if !content.is_empty() {
+            content.parse::<Token![,]>()?;
+        }
+        Ok(EqBy(path))
+    }
+}
+
+fn get_eq_by(field: &Field) -> Option<Path> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("wrap"))
+        .filter_map(|attr| syn::parse2::<EqBy>(attr.tts.clone()).ok())
+        .map(|eq_by| eq_by.0)
+        .next()
+}
+
+// PartialEq, Eq, PartialOrd, Ord and Hash by the wrapped field alone, ignoring the rest.
+fn compare_inner(input: DeriveInput, trait_name: &str) -> Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        std,
+        ..
+    } = Details::from_input(&input.ident, field);
+    let key = |value: TokenStream2| match get_eq_by(field) {
+        Some(eq_by) => quote!(&#eq_by(&#value.#field_name)),
+        None => quote!(&#value.#field_name),
+    };
+    let this = key(quote!(self));
+    let other = key(quote!(other));
+
+    Ok(match trait_name {
+        "WrapperPartialEq" => quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::cmp::PartialEq for #struct_name #ty_generics #where_clause {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    #std::cmp::PartialEq::eq(#this, #other)
+                }
+            }
+        },
+        "WrapperEq" => quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::cmp::Eq for #struct_name #ty_generics #where_clause {}
+        },
+        "WrapperPartialOrd" => quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::cmp::PartialOrd for #struct_name #ty_generics #where_clause {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> #std::option::Option<#std::cmp::Ordering> {
+                    #std::cmp::PartialOrd::partial_cmp(#this, #other)
+                }
+            }
+        },
+        "WrapperOrd" => quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::cmp::Ord for #struct_name #ty_generics #where_clause {
+                #[inline]
+                fn cmp(&self, other: &Self) -> #std::cmp::Ordering {
+                    #std::cmp::Ord::cmp(#this, #other)
+                }
+            }
+        },
+        _ => quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::hash::Hash for #struct_name #ty_generics #where_clause {
+                #[inline]
+                fn hash<H: #std::hash::Hasher>(&self, state: &mut H) {
+                    #std::hash::Hash::hash(#this, state)
+                }
+            }
+        },
+    })
+}
+
+// The unsized type a field can also be compared as: `[T]` for arrays, boxed slices and vectors, `str` for strings.
+fn unsized_form(ty: &Type) -> Option<Type> {
+    if let Type::Array(_) = *ty {
+        return Some(array_to_slice(ty.clone()));
+    }
+    if let Some(inner) = type_argument(ty, "Box") {
+        if let Type::Slice(_) = *inner {
+            return Some(inner.clone());
+        }
+    }
+    if let Some(elem) = type_argument(ty, "Vec") {
+        return Some(parse_quote!([#elem]));
+    }
+    match *ty {
+        Type::Path(ref path) if path.path.segments.iter().last()?.ident == "String" => {
+            Some(parse_quote!(str))
+        }
+        _ => None,
+    }
+}
+
+// PartialEq or PartialOrd in both directions between the wrapper and its inner type.
+fn cross_compare_inner(input: DeriveInput, trait_name: &str) -> Result<TokenStream2> {
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (ref_generics, lifetime) = generics_with_lifetime(&input.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+    let this = quote!(#struct_name #ty_generics);
+    let (trait_path, method, output) = if trait_name == "InnerPartialEq" {
+        (quote!(#std::cmp::PartialEq), quote!(eq), quote!(bool))
+    } else {
+        (
+            quote!(#std::cmp::PartialOrd),
+            quote!(partial_cmp),
+            quote!(#std::option::Option<#std::cmp::Ordering>),
+        )
+    };
+
+    // Each entry is the generics, the other type, the field access, and whether the other value is behind a reference.
+    let mut pairs = vec![(
+        &impl_generics,
+        quote!(#field_type),
+        quote!(#field_name),
+        false,
+    )];
+    if let Some(form) = unsized_form(field_type) {
+        pairs.push((
+            &impl_generics,
+            quote!(#form),
+            quote!(#field_name[..]),
+            false,
+        ));
+        pairs.push((
+            &ref_impl_generics,
+            quote!(&#lifetime #form),
+            quote!(#field_name[..]),
+            true,
+        ));
+    }
+    let impls = pairs.into_iter().map(|(generics, other, field, deref)| {
+        let deref = if deref { quote!(*) } else { quote!() };
+        quote! {
+            #[allow(unused_qualifications)]
+            impl #generics #trait_path<#other> for #this #where_clause {
+                #[inline]
+                fn #method(&self, other: &#other) -> #output {
+                    #trait_path::#method(&self.#field, #deref other)
+                }
+            }
+
+            #[allow(unused_qualifications)]
+            impl #generics #trait_path<#this> for #other #where_clause {
+                #[inline]
+                fn #method(&self, other: &#this) -> #output {
+                    #trait_path::#method(#deref self, &other.#field)
+                }
+            }
+        }
+    });
+
+    Ok(quote!(#(#impls)*))
+}
+
+// Default, Debug, Hash and Clone for structs wrapping arrays of any length.
+// The array is handled as a slice where possible, Default and Clone rely on arrays being Copy for every length,
+// so they require the element to be Copy.
+fn array_trait_inner(input: DeriveInput, trait_name: &str) -> Result<TokenStream2> {
+    let field = get_field(&input, trait_name)?;
+    let Details {
+        struct_name,
+        field_name,
+        field_type,
+        std,
+    } = Details::from_input(&input.ident, field);
+    let (elem, len) = match *field_type {
+        Type::Array(ref array) => (&array.elem, &array.len),
+        _ => {
+            return Err(Error::new_spanned(
+                field_type,
+                format!("Deriving {} requires an array field", trait_name),
+            ))
+        }
+    };
+    let mut generics = input.generics.clone();
+    match trait_name {
+        "ArrayDefault" => generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#elem: #std::marker::Copy + #std::default::Default)),
+        "ArrayClone" => generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#elem: #std::marker::Copy)),
+        _ => (),
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => unreachable!("get_field accepts only structs"),
+    };
+    // Every field in order, with the wrapped array as a slice.
+    let values: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let member = f.ident.as_ref().map_or_else(
+                || Index::from(i).into_token_stream(),
+                ToTokens::into_token_stream,
+            );
+            if std::ptr::eq(f, field) {
+                quote!(&self.#member[..])
+            } else {
+                quote!(&self.#member)
+            }
+        })
+        .collect();
+
+    let body = match trait_name {
+        "ArrayDefault" => {
+            let others = get_other_fields(&input, field)
+                .into_iter()
+                .map(|other| quote!(#other: #std::default::Default::default()));
+            quote! {
+                impl #impl_generics #std::default::Default for #struct_name #ty_generics #where_clause {
+                    #[inline]
+                    fn default() -> Self {
+                        #struct_name {
+                            #field_name: [<#elem as #std::default::Default>::default(); #len],
+                            #(#others,)*
+                        }
+                    }
+                }
+            }
+        }
+        "ArrayDebug" => {
+            let name = struct_name.to_string();
+            let debug = match *fields {
+                Fields::Named(_) => {
+                    let names = fields.iter().map(|f| f.ident.as_ref().unwrap().to_string());
+                    quote!(f.debug_struct(#name)#(.field(#names, &#values))*.finish())
+                }
+                Fields::Unnamed(_) => quote!(f.debug_tuple(#name)#(.field(&#values))*.finish()),
+                Fields::Unit => unreachable!("get_field rejects empty structs"),
+            };
+            quote! {
+                impl #impl_generics #std::fmt::Debug for #struct_name #ty_generics #where_clause {
+                    fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
+                        #debug
                     }
                 }
-                for from in froms {
-                    let field_type = from.0;
-                    let postfix = from.1;
-                    res = quote! {
-                        #res
-                        #[allow(unused_qualifications)]
-                        impl #impl_generics #std::convert::From<#field_type> for #enum_name #ty_generics #where_clause {
-                            #[inline]
-                            fn from(inner: #field_type) -> Self {
-                                #enum_name::#variant_name#postfix
-                            }
+            }
+        }
+        "ArrayHash" => {
+            let hashes = values
+                .iter()
+                .map(|value| quote!(#std::hash::Hash::hash(#value, state);));
+            quote! {
+                impl #impl_generics #std::hash::Hash for #struct_name #ty_generics #where_clause {
+                    fn hash<H: #std::hash::Hasher>(&self, state: &mut H) {
+                        #(#hashes)*
+                    }
+                }
+            }
+        }
+        _ => {
+            let others = get_other_fields(&input, field)
+                .into_iter()
+                .map(|other| quote!(#other: #std::clone::Clone::clone(&self.#other)));
+            quote! {
+                impl #impl_generics #std::clone::Clone for #struct_name #ty_generics #where_clause {
+                    #[inline]
+                    fn clone(&self) -> Self {
+                        #struct_name {
+                            #field_name: self.#field_name,
+                            #(#others,)*
                         }
-                    };
+                    }
                 }
             }
         }
-    }
-    Ok(res)
+    };
+
+    Ok(quote! {
+        #[allow(unused_qualifications)]
+        #body
+    })
 }
 
-fn from_inner_struct(input: &DeriveInput) -> Result<TokenStream2> {
+fn assign_op_inner(input: DeriveInput, trait_name: &str, method: &str) -> Result<TokenStream2> {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let field = get_field(&input, "From")?;
+    let field = get_field(&input, trait_name)?;
     let Details {
         struct_name,
         field_name,
         field_type,
         std,
-        ..
     } = Details::from_input(&input.ident, field);
+    let trait_name = Ident::new(trait_name, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
 
     Ok(quote! {
         #[allow(unused_qualifications)]
-        impl #impl_generics #std::convert::From<#field_type> for #struct_name #ty_generics #where_clause {
+        impl #impl_generics #std::ops::#trait_name for #struct_name #ty_generics #where_clause {
             #[inline]
-            fn from(wrap: #field_type) -> Self {
-                #struct_name {#field_name: wrap}
+            fn #method(&mut self, rhs: Self) {
+                #std::ops::#trait_name::#method(&mut self.#field_name, rhs.#field_name)
             }
         }
-    })
-}
-
-fn display_inner(input: DeriveInput) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let struct_name = &input.ident;
-    let std = std();
-    let display_from = get_meta_value(
-        &input.attrs,
-        "Display",
-        "display_from",
-        Some("#[display_from(Debug)]`"),
-    )?
-    .expect("provided example, should always return a value if succeeded.");
 
-    Ok(quote! {
         #[allow(unused_qualifications)]
-        impl #impl_generics #std::fmt::Display for #struct_name #ty_generics #where_clause {
+        impl #impl_generics #std::ops::#trait_name<#field_type> for #struct_name #ty_generics #where_clause {
             #[inline]
-            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
-                #std::fmt::#display_from::fmt(&self, f)
+            fn #method(&mut self, rhs: #field_type) {
+                #std::ops::#trait_name::#method(&mut self.#field_name, rhs)
             }
         }
     })
 }
 
-fn lowerhexiter_inner(input: DeriveInput) -> Result<TokenStream2> {
-    let field = get_field(&input, "LowerHexIter")?;
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let Details {
-        struct_name,
-        field_name,
-        std,
-        ..
-    } = Details::from_input(&input.ident, field);
+const BINARY_OPS: &[&str] = &[
+    "Add", "Sub", "Mul", "Div", "Rem", "BitAnd", "BitOr", "BitXor", "Shl", "Shr",
+];
 
-    Ok(quote! {
-        #[allow(unused_qualifications)]
-        impl #impl_generics #std::fmt::LowerHex<> for #struct_name #ty_generics #where_clause {
-            #[inline]
-            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
-                for ch in self.#field_name.iter() {
-                    #std::fmt::LowerHex::fmt(&ch, f)?;
+#[derive(Clone, Copy, PartialEq)]
+enum Operand {
+    Wrapper,
+    Inner,
+}
+
+impl Parse for Operand {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![Self]) {
+            input.parse::<Token![Self]>()?;
+            return Ok(Operand::Wrapper);
+        }
+        let name: Ident = input.parse()?;
+        if name == "Inner" {
+            Ok(Operand::Inner)
+        } else {
+            Err(Error::new_spanned(
+                &name,
+                "derive_wrapper: an operand can be either `Self` or `Inner`",
+            ))
+        }
+    }
+}
+
+// A single `Trait<Rhs> -> Output` entry of the ops attribute.
+struct OpSpec {
+    trait_name: Ident,
+    rhs: Operand,
+    output: Operand,
+}
+
+impl Parse for OpSpec {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let trait_name: Ident = input.parse()?;
+        if !BINARY_OPS.iter().any(|op| trait_name == op) {
+            return Err(Error::new_spanned(
+                &trait_name,
+                format!(
+                    "derive_wrapper: unsupported operator `{}`. Supported operators: {}",
+                    trait_name,
+                    BINARY_OPS.join(", ")
+                ),
+            ));
+        }
+        input.parse::<Token![<]>()?;
+        let rhs = input.parse()?;
+        input.parse::<Token![>]>()?;
+        input.parse::<Token![->]>()?;
+        let output = input.parse()?;
+        Ok(OpSpec {
+            trait_name,
+            rhs,
+            output,
+        })
+    }
+}
+
+struct OpsAttr(Vec<OpSpec>);
+
+impl Parse for OpsAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let specs: Punctuated<OpSpec, Token![,]> = Punctuated::parse_terminated(&content)?;
+        let specs: Vec<_> = specs.into_iter().collect();
+        for (i, spec) in specs.iter().enumerate() {
+            if specs[..i]
+                .iter()
+                .any(|other| other.trait_name == spec.trait_name && other.rhs == spec.rhs)
+            {
+                return Err(Error::new_spanned(
+                    &spec.trait_name,
+                    format!(
+                        "derive_wrapper: `{}` is specified twice for the same right-hand side",
+                        spec.trait_name
+                    ),
+                ));
+            }
+        }
+        Ok(OpsAttr(specs))
+    }
+}
+
+impl OpsAttr {
+    pub fn from_attrs(input: &DeriveInput) -> Result<Self> {
+        let mut res = None;
+        for attr in &input.attrs {
+            if attr.path.is_ident("ops") {
+                if res.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: supports only a single ops attribute",
+                    ));
                 }
-                #std::result::Result::Ok(())
+                res = Some(syn::parse2(attr.tts.clone())?);
             }
         }
-    })
+        res.ok_or_else(|| {
+            Error::new_spanned(
+                input,
+                "Deriving Ops requires an ops attribute. Try: `#[ops(Mul<Inner> -> Self)]`",
+            )
+        })
+    }
 }
 
-fn lowerhex_inner(input: DeriveInput) -> Result<TokenStream2> {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let field = get_field(&input, "LowerHex")?;
+fn ops_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let OpsAttr(specs) = OpsAttr::from_attrs(&input)?;
+    let field = get_field(&input, "Ops")?;
     let Details {
         struct_name,
         field_name,
+        field_type,
         std,
-        ..
     } = Details::from_input(&input.ident, field);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let this = quote!(#struct_name #ty_generics);
 
-    Ok(quote! {
-        #[allow(unused_qualifications)]
-        impl #impl_generics #std::fmt::LowerHex for #struct_name #ty_generics #where_clause {
-            #[inline]
-            fn fmt(&self, f: &mut #std::fmt::Formatter) -> #std::fmt::Result {
-                #std::fmt::LowerHex::fmt(&self.#field_name, f)
+    let impls = specs.iter().map(|spec| {
+        let trait_name = &spec.trait_name;
+        let method = Ident::new(&trait_name.to_string().to_lowercase(), Span::call_site());
+        let (rhs_ty, rhs) = match spec.rhs {
+            Operand::Wrapper => (this.clone(), quote!(rhs.#field_name)),
+            Operand::Inner => (quote!(#field_type), quote!(rhs)),
+        };
+        let value = quote!(#std::ops::#trait_name::#method(self.#field_name, #rhs));
+        let (output_ty, output) = match spec.output {
+            Operand::Wrapper => (
+                this.clone(),
+                rebuild_struct(&input, field, value, quote!(self), false),
+            ),
+            Operand::Inner => (quote!(#field_type), value),
+        };
+        quote! {
+            #[allow(unused_qualifications)]
+            impl #impl_generics #std::ops::#trait_name<#rhs_ty> for #this #where_clause {
+                type Output = #output_ty;
+                #[inline]
+                fn #method(self, rhs: #rhs_ty) -> Self::Output {
+                    #output
+                }
             }
         }
-    })
+    });
+
+    Ok(quote!(#(#impls)*))
 }
 
-#[allow(non_snake_case)]
-fn generate_index_from_T(
-    output: Option<TokenStream2>,
-    T: TokenStream2,
+const PRIMITIVE_INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+// The name of the primitive integer `ty` is, if it is one.
+fn primitive_integer(ty: &Type) -> Option<&Ident> {
+    match *ty {
+        Type::Path(ref path) if path.qself.is_none() => path
+            .path
+            .segments
+            .iter()
+            .last()
+            .map(|segment| &segment.ident)
+            .filter(|ident| PRIMITIVE_INTEGERS.iter().any(|name| *ident == name)),
+        _ => None,
+    }
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match *ty {
+        Type::Path(ref path) => match path.path.segments.iter().last() {
+            Some(segment) => segment.ident == "PhantomData",
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+// The other fields of a struct built in a const context, which is possible only when they are PhantomData.
+fn constant_other_fields(
     input: &DeriveInput,
     field: &Field,
-) -> TokenStream2 {
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    attribute_name: &str,
+) -> Result<Vec<TokenStream2>> {
+    if let Some(ty) = other_field_types(input, field)
+        .into_iter()
+        .find(|ty| !is_phantom_data(ty))
+    {
+        return Err(Error::new_spanned(
+            ty,
+            format!(
+                "derive_wrapper: {} constants require the other fields to be PhantomData",
+                attribute_name
+            ),
+        ));
+    }
+    let std = std();
+    Ok(get_other_fields(input, field)
+        .into_iter()
+        .map(|other| quote!(#other: #std::marker::PhantomData))
+        .collect())
+}
+
+fn integer_ops_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let field = get_field(&input, "IntegerOps")?;
     let Details {
         struct_name,
         field_name,
         field_type,
         std,
     } = Details::from_input(&input.ident, field);
-    let field_type = array_to_slice(field_type.clone());
-    let output = output.unwrap_or_else(|| quote!(<#field_type as #std::ops::Index<#T>>::Output));
-    quote! {
-        #[allow(unused_qualifications)]
-        impl #impl_generics #std::ops::Index<#T> for #struct_name #ty_generics #where_clause {
-            type Output = #output;
-            #[inline]
-            fn index(&self, index: #T) -> &Self::Output {
-                &self.#field_name[index]
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let integer = primitive_integer(field_type).ok_or_else(|| {
+        Error::new_spanned(
+            field_type,
+            format!(
+                "Deriving IntegerOps requires a primitive integer field. Supported types: {}",
+                PRIMITIVE_INTEGERS.join(", ")
+            ),
+        )
+    })?;
+
+    let mut consts = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("integer_ops"))
+    {
+        let options = find_meta_value(attr, "integer_ops", "#[integer_ops(consts)]")?;
+        for option in options.name {
+            match option {
+                Member::Named(ref name) if name == "consts" => consts = true,
+                _ => {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: unknown integer_ops option. Try: `#[integer_ops(consts)]`",
+                    ))
+                }
             }
         }
     }
-}
 
-fn index_inner(input: DeriveInput) -> Result<TokenStream2> {
-    let field = get_field(&input, "Index")?;
-    let index_output = get_meta_value(&input.attrs, "Index", "index_output", None)?
-        .map(ToTokens::into_token_stream);
-    let std = std();
+    let methods = ["add", "sub", "mul"].iter().map(|op| {
+        let checked = Ident::new(&format!("checked_{}", op), Span::call_site());
+        let saturating = Ident::new(&format!("saturating_{}", op), Span::call_site());
+        let wrapping = Ident::new(&format!("wrapping_{}", op), Span::call_site());
+        let checked_value = rebuild_struct(&input, field, quote!(value), quote!(self), false);
+        let saturating_value = rebuild_struct(
+            &input,
+            field,
+            quote!(self.#field_name.#saturating(rhs.#field_name)),
+            quote!(self),
+            false,
+        );
+        let wrapping_value = rebuild_struct(
+            &input,
+            field,
+            quote!(self.#field_name.#wrapping(rhs.#field_name)),
+            quote!(self),
+            false,
+        );
+        quote! {
+            #[inline]
+            pub fn #checked(self, rhs: Self) -> #std::option::Option<Self> {
+                self.#field_name
+                    .#checked(rhs.#field_name)
+                    .map(|value| #checked_value)
+            }
 
-    let slice_output = index_output.clone().map(|index| quote!([#index]));
+            #[inline]
+            pub fn #saturating(self, rhs: Self) -> Self {
+                #saturating_value
+            }
 
-    let index_usize = generate_index_from_T(index_output, quote!(usize), &input, field);
-    let index_range_usize = generate_index_from_T(
-        slice_output.clone(),
-        quote!(#std::ops::Range<usize>),
-        &input,
-        field,
-    );
-    let index_range_to_usize = generate_index_from_T(
-        slice_output.clone(),
-        quote!(#std::ops::RangeTo<usize>),
-        &input,
-        field,
-    );
-    let index_range_from_usize = generate_index_from_T(
-        slice_output.clone(),
-        quote!(#std::ops::RangeFrom<usize>),
-        &input,
-        field,
-    );
-    let index_range_full =
-        generate_index_from_T(slice_output, quote!(#std::ops::RangeFull), &input, field);
+            #[inline]
+            pub fn #wrapping(self, rhs: Self) -> Self {
+                #wrapping_value
+            }
+        }
+    });
+
+    let consts = if consts {
+        let others = constant_other_fields(&input, field, "integer_ops")?;
+        let others = &others;
+        let constant =
+            |value: TokenStream2| quote!(#struct_name { #field_name: #value, #(#others,)* });
+        let min = constant(quote!(#integer::MIN));
+        let max = constant(quote!(#integer::MAX));
+        let zero = constant(quote!(0));
+        quote! {
+            pub const MIN: Self = #min;
+            pub const MAX: Self = #max;
+            pub const ZERO: Self = #zero;
+        }
+    } else {
+        quote!()
+    };
 
     Ok(quote! {
-        #index_usize
-        #index_range_usize
-        #index_range_to_usize
-        #index_range_from_usize
-        #index_range_full
+        #[allow(unused_qualifications)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            #consts
+            #(#methods)*
+        }
     })
 }
 
@@ -352,7 +3726,7 @@ fn get_meta_value(
             ));
         }
         if mv.found {
-            if let Some(trait_name) = mv.name.get(0) {
+            if let Some(trait_name) = mv.name.first() {
                 traits_found.push(trait_name.clone());
             } else {
                 return Err(Error::new_spanned(attr, format!("derive_wrapper: when using the {} attribute on the struct you must specify the trait you want to use to implement {}", attribute_name, trait_name)));
@@ -379,6 +3753,190 @@ fn get_meta_value(
     }
 }
 
+#[derive(Default)]
+struct HexOptions {
+    separator: Option<LitStr>,
+    groups: Vec<usize>,
+    element: Option<(Ident, Ident)>,
+}
+
+impl Parse for HexOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let mut options = HexOptions::default();
+        while !content.is_empty() {
+            let name: Ident = content.parse()?;
+            content.parse::<Token![=]>()?;
+            if name == "separator" {
+                options.separator = Some(content.parse()?);
+            } else if name == "groups" {
+                let list;
+                bracketed!(list in content);
+                for size in Punctuated::<LitInt, Token![,]>::parse_terminated(&list)? {
+                    if size.value() == 0 {
+                        return Err(Error::new(
+                            size.span(),
+                            "derive_wrapper: hex groups can't be empty",
+                        ));
+                    }
+                    options.groups.push(size.value() as usize);
+                }
+            } else if name == "element" {
+                let order: LitStr = content.parse()?;
+                let methods = match order.value().as_str() {
+                    "be" => ("to_be_bytes", "from_be_bytes"),
+                    "le" => ("to_le_bytes", "from_le_bytes"),
+                    _ => {
+                        return Err(Error::new(
+                            order.span(),
+                            "derive_wrapper: hex element byte order must be \"be\" or \"le\"",
+                        ))
+                    }
+                };
+                options.element = Some((
+                    Ident::new(methods.0, order.span()),
+                    Ident::new(methods.1, order.span()),
+                ));
+            } else {
+                return Err(Error::new(name.span(), format!("derive_wrapper: unknown hex option `{}`. Try: `#[hex(separator = \":\", groups = [4, 2])]`", name)));
+            }
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        if options.separator.is_none() && !options.groups.is_empty() {
+            return Err(Error::new(Span::call_site(), "derive_wrapper: hex groups require a separator. Try: `#[hex(groups = [4, 2], separator = \"-\")]`"));
+        }
+        Ok(options)
+    }
+}
+
+impl HexOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut res = None;
+        for attr in attrs {
+            if attr.path.is_ident("hex") {
+                if res.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "derive_wrapper: supports only a single hex attribute",
+                    ));
+                }
+                res = Some(syn::parse2(attr.tts.clone())?);
+            }
+        }
+        Ok(res.unwrap_or_default())
+    }
+
+    // The bytes of a single element, either the element itself or its bytes in the requested order.
+    pub fn generate_unit(&self, element: TokenStream2) -> TokenStream2 {
+        match self.element {
+            Some((ref to_bytes, _)) => quote!(#element.#to_bytes()),
+            None => quote!([*#element]),
+        }
+    }
+
+    // Whether elements are encoded byte by byte: always with the element option, otherwise only for `u8` elements.
+    pub fn encodes_bytes(&self, field_type: &Type) -> bool {
+        self.element.is_some()
+            || match iter_element(field_type) {
+                Some(elem) => is_u8(&elem),
+                None => false,
+            }
+    }
+
+    // The number of bytes in a single element.
+    pub fn generate_unit_len(&self, field_type: &Type, field_name: &TokenStream2) -> TokenStream2 {
+        let std = std();
+        if self.encodes_bytes(field_type) {
+            let unit = self.generate_unit(quote!(ch));
+            quote!(self.#field_name.iter().next().map_or(0, |ch| #unit.len()))
+        } else {
+            quote!(self.#field_name.iter().next().map_or(0, #std::mem::size_of_val))
+        }
+    }
+
+    pub fn generate_consts(&self) -> TokenStream2 {
+        match self.separator {
+            Some(ref separator) => {
+                let groups = if self.groups.is_empty() {
+                    vec![1]
+                } else {
+                    self.groups.clone()
+                };
+                quote! {
+                    const SEPARATOR: &str = #separator;
+                    const GROUPS: &[usize] = &[#(#groups),*];
+                }
+            }
+            None => quote!(),
+        }
+    }
+
+    // Returns the state initialization and the per element step, which runs `action` whenever a group ends.
+    // The last group size repeats until the end of the value.
+    pub fn generate_separator(
+        &self,
+        std: &Path,
+        action: TokenStream2,
+    ) -> (TokenStream2, TokenStream2) {
+        if self.separator.is_none() {
+            return (quote!(), quote!());
+        }
+        let init = quote! {
+            let mut group = 0;
+            let mut in_group = GROUPS[0];
+        };
+        let step = quote! {
+            if in_group == 0 {
+                #action
+                group = #std::cmp::min(group + 1, GROUPS.len() - 1);
+                in_group = GROUPS[group];
+            }
+            in_group -= 1;
+        };
+        (init, step)
+    }
+}
+
+// The element type of arrays, slices, boxed slices and vectors.
+fn iter_element(ty: &Type) -> Option<Type> {
+    match *ty {
+        Type::Reference(ref reference) => iter_element(&reference.elem),
+        Type::Slice(ref slice) => Some((*slice.elem).clone()),
+        _ => match unsized_form(ty) {
+            Some(Type::Slice(slice)) => Some(*slice.elem),
+            _ => None,
+        },
+    }
+}
+
+fn is_u8(ty: &Type) -> bool {
+    match *ty {
+        Type::Path(ref path) => path.qself.is_none() && path.path.is_ident("u8"),
+        _ => false,
+    }
+}
+
+// Returns the generic argument of `ty` if it's the type `name<T>`, like `Option<T>`.
+fn type_argument<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let segment = match *ty {
+        Type::Path(ref path) if path.qself.is_none() => path.path.segments.iter().last()?,
+        _ => return None,
+    };
+    if segment.ident != name {
+        return None;
+    }
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref arguments) => match arguments.args.iter().next() {
+            Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn array_to_slice(ty: Type) -> Type {
     if let Type::Array(arr) = ty {
         Type::Slice(TypeSlice {
@@ -416,24 +3974,24 @@ fn get_field<'a>(input: &'a DeriveInput, trait_name: &str) -> Result<&'a Field>
         Data::Struct(ref data) => &data.fields,
         _ => {
             return Err(Error::new_spanned(
-                &input,
+                input,
                 format!("Deriving {} is supported only in structs", trait_name),
             ))
         }
     };
 
     if fields.iter().len() > 1 {
-        let mut marked_fields = parse_outer_attributes(&input.attrs, &fields)?;
-        marked_fields.extend(parse_field_attributes(&fields)?);
+        let mut marked_fields = parse_outer_attributes(&input.attrs, fields)?;
+        marked_fields.extend(parse_field_attributes(fields)?);
         match marked_fields.len() {
             1 => Ok(marked_fields.pop().unwrap()),
-            0 => Err(Error::new_spanned(&input, format!("Deriving {} for a struct with multiple fields requires specifying a wrap attribute", trait_name))),
-            _ => Err(Error::new_spanned(&input, format!("Deriving {} supports only a single wrap attribute", trait_name))),
+            0 => Err(Error::new_spanned(input, format!("Deriving {} for a struct with multiple fields requires specifying a wrap attribute", trait_name))),
+            _ => Err(Error::new_spanned(input, format!("Deriving {} supports only a single wrap attribute", trait_name))),
         }
     } else {
         fields.iter().next().ok_or_else(|| {
             Error::new_spanned(
-                &input,
+                input,
                 format!(
                     "Deriving {} for an empty struct isn't supported",
                     trait_name
@@ -443,6 +4001,36 @@ fn get_field<'a>(input: &'a DeriveInput, trait_name: &str) -> Result<&'a Field>
     }
 }
 
+fn get_other_fields(input: &DeriveInput, field: &Field) -> Vec<TokenStream2> {
+    match input.data {
+        Data::Struct(ref data) => data
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|&(_, f)| !std::ptr::eq(f, field))
+            .map(|(i, f)| {
+                f.ident.as_ref().map_or_else(
+                    || Index::from(i).into_token_stream(),
+                    ToTokens::into_token_stream,
+                )
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn other_field_types<'a>(input: &'a DeriveInput, field: &Field) -> Vec<&'a Type> {
+    match input.data {
+        Data::Struct(ref data) => data
+            .fields
+            .iter()
+            .filter(|f| !std::ptr::eq(*f, field))
+            .map(|f| &f.ty)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 fn extract_types_from_potential_tupled_attribute(attr: &Attribute) -> Result<Vec<Type>> {
     let ty: Type = syn::parse2(attr.tts.clone())?;
     Ok(match ty {
@@ -487,14 +4075,14 @@ impl MetaValue {
     }
 
     pub fn get_first_name(&self) -> Option<String> {
-        self.name.get(0).map(|name| match *name {
+        self.name.first().map(|name| match *name {
             Member::Unnamed(ref index) => index.index.to_string(),
             Member::Named(ref ident) => ident.to_string(),
         })
     }
 
     pub fn get_first_index(&self) -> Option<u32> {
-        self.name.get(0).and_then(|n| match *n {
+        self.name.first().and_then(|n| match *n {
             Member::Unnamed(ref i) => Some(i.index),
             Member::Named(_) => None,
         })
@@ -502,6 +4090,15 @@ impl MetaValue {
 }
 fn find_meta_value(attr: &Attribute, name: &str, example: &str) -> Result<MetaValue> {
     let mut res = MetaValue::default();
+    // Other attributes, like `#[hex(groups = [..])]`, aren't necessarily valid meta.
+    if !attr.path.is_ident(name) {
+        return Ok(res);
+    }
+    // `#[wrap(eq_by = path)]` isn't a valid meta, it marks the field like a plain `#[wrap]`.
+    if attr.path.is_ident("wrap") && syn::parse2::<EqBy>(attr.tts.clone()).is_ok() {
+        res.found = name == "wrap";
+        return Ok(res);
+    }
     match attr.parse_meta() {
         Ok(meta) => {
             if meta.name() == name {
@@ -539,7 +4136,7 @@ fn parse_outer_attributes<'a>(attrs: &[Attribute], fields: &'a Fields) -> Result
                 if let Some(field) = fields.iter().nth(index as usize) {
                     res.push(field);
                 } else {
-                    return Err(Error::new_spanned(&fields, format!("derive_wrapper: there's no field no. {} in the struct or it's not a tuple", index)));
+                    return Err(Error::new_spanned(fields, format!("derive_wrapper: there's no field no. {} in the struct or it's not a tuple", index)));
                 }
             } else if let Some(lit_name) = mv.get_first_name() {
                 let mut found = false;
@@ -554,12 +4151,12 @@ fn parse_outer_attributes<'a>(attrs: &[Attribute], fields: &'a Fields) -> Result
                 }
                 if !found {
                     return Err(Error::new_spanned(
-                        &fields,
+                        fields,
                         format!("derive_wrapper: field {} doesn't exist", lit_name),
                     ));
                 }
             } else {
-                return Err(Error::new_spanned(&fields, "derive_wrapper: when using the wrap attribute on the struct you must specify the field name"));
+                return Err(Error::new_spanned(fields, "derive_wrapper: when using the wrap attribute on the struct you must specify the field name"));
             }
         }
     }
@@ -575,12 +4172,12 @@ fn parse_field_attributes(fields: &Fields) -> Result<Vec<&Field>> {
                 if let Some(ref ident) = field.ident {
                     if let Some(lit) = mv.get_first_name() {
                         if ident != &lit {
-                            return Err(Error::new_spanned(&field, format!("derive_wrapper: The provided field name doesn't match the field name it's above: `{} != {}`", lit, ident)));
+                            return Err(Error::new_spanned(field, format!("derive_wrapper: The provided field name doesn't match the field name it's above: `{} != {}`", lit, ident)));
                         }
                     }
                     res.push(field)
                 } else {
-                    return Err(Error::new_spanned(&field, "derive_wrapper doesn't yet support attributes on unnamed fields (Please file an issue)"));
+                    return Err(Error::new_spanned(field, "derive_wrapper doesn't yet support attributes on unnamed fields (Please file an issue)"));
                 }
             }
         }